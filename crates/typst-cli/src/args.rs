@@ -109,6 +109,11 @@ pub struct CompileCommand {
     #[arg(long = "ppi", default_value_t = 144.0)]
     pub ppi: f32,
 
+    /// The PDF standard that the output conforms to. Ignored for PNG and SVG
+    /// export
+    #[arg(long = "pdf-standard", default_value_t = PdfStandard::V_1_7)]
+    pub pdf_standard: PdfStandard,
+
     /// Produces performance timings of the compilation process (experimental)
     ///
     /// The resulting JSON file can be loaded into a tracing tool such as
@@ -436,6 +441,28 @@ pub struct UpdateCommand {
     pub backup_path: Option<PathBuf>,
 }
 
+/// A PDF standard that the output conforms to.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, ValueEnum)]
+#[allow(non_camel_case_types)]
+pub enum PdfStandard {
+    /// PDF 1.7.
+    #[value(name = "1.7")]
+    V_1_7,
+    /// PDF/A-2b, for archival. Declares sRGB as the output intent, but does
+    /// not check that the document conforms to PDF/A otherwise.
+    #[value(name = "a-2b")]
+    A_2b,
+}
+
+impl Display for PdfStandard {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.to_possible_value()
+            .expect("no values are skipped")
+            .get_name()
+            .fmt(f)
+    }
+}
+
 /// Which format to use for the generated output file.
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, ValueEnum)]
 pub enum OutputFormat {
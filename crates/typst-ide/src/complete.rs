@@ -882,6 +882,12 @@ fn code_completions(ctx: &mut CompletionContext, hash: bool) {
         "Sets style properties on an element.",
     );
 
+    ctx.snippet_completion(
+        "set default rule",
+        "set default ${}",
+        "Sets default style properties that regular set rules override.",
+    );
+
     ctx.snippet_completion(
         "show rule",
         "show ${}",
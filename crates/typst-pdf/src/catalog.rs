@@ -2,7 +2,7 @@ use std::num::NonZeroUsize;
 
 use ecow::eco_format;
 use pdf_writer::{
-    types::Direction, writers::PageLabel, Finish, Name, Pdf, Ref, Str, TextStr,
+    types::Direction, writers::PageLabel, Filter, Finish, Name, Pdf, Ref, Str, TextStr,
 };
 use xmp_writer::{DateTime, LangId, RenditionClass, Timezone, XmpWriter};
 
@@ -10,14 +10,15 @@ use typst::foundations::{Datetime, Smart};
 use typst::layout::Dir;
 use typst::text::Lang;
 
-use crate::WithEverything;
-use crate::{hash_base64, outline, page::PdfPageLabel};
+use crate::color::SRGB_ICC_DEFLATED;
+use crate::{hash_base64, outline, page::PdfPageLabel, PdfStandard, WithEverything};
 
 /// Write the document catalog.
 pub fn write_catalog(
     ctx: WithEverything,
     ident: Smart<&str>,
     timestamp: Option<Datetime>,
+    standard: PdfStandard,
     pdf: &mut Pdf,
     alloc: &mut Ref,
 ) {
@@ -132,6 +133,19 @@ pub fn write_catalog(
         .pair(Name(b"Type"), Name(b"Metadata"))
         .pair(Name(b"Subtype"), Name(b"XML"));
 
+    // PDF/A requires an output intent. It shares the ICC profile with the
+    // sRGB color space, which is only written if any content uses it.
+    let output_profile_ref = (standard == PdfStandard::A_2b).then(|| {
+        ctx.globals.color_functions.srgb().unwrap_or_else(|| {
+            let profile_ref = alloc.bump();
+            pdf.icc_profile(profile_ref, &SRGB_ICC_DEFLATED)
+                .n(3)
+                .range([0.0, 1.0, 0.0, 1.0, 0.0, 1.0])
+                .filter(Filter::FlateDecode);
+            profile_ref
+        })
+    });
+
     // Write the document catalog.
     let catalog_ref = alloc.bump();
     let mut catalog = pdf.catalog(catalog_ref);
@@ -167,6 +181,20 @@ pub fn write_catalog(
         catalog.lang(TextStr(lang.as_str()));
     }
 
+    // Declare sRGB as the intended output color space, so that viewers and
+    // printers know how to interpret device colors.
+    if let Some(output_profile_ref) = output_profile_ref {
+        let mut intents = catalog.insert(Name(b"OutputIntents")).array();
+        let mut intent = intents.push().dict();
+        intent.pair(Name(b"Type"), Name(b"OutputIntent"));
+        intent.pair(Name(b"S"), Name(b"GTS_PDFA1"));
+        intent.pair(Name(b"OutputConditionIdentifier"), TextStr("sRGB"));
+        intent.pair(Name(b"Info"), TextStr("sRGB IEC61966-2.1"));
+        intent.pair(Name(b"DestOutputProfile"), output_profile_ref);
+        intent.finish();
+        intents.finish();
+    }
+
     catalog.finish();
 }
 
@@ -276,3 +304,69 @@ fn xmp_date(datetime: Datetime, tz: bool) -> Option<xmp_writer::DateTime> {
         timezone: if tz { Some(Timezone::Utc) } else { None },
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use typst::layout::{Abs, Frame, FrameItem, Page, Point, Size};
+    use typst::model::Document;
+    use typst::syntax::Span;
+    use typst::visualize::{Color, Geometry};
+
+    use super::*;
+
+    #[test]
+    fn test_pdf_reproducible() {
+        let mut document = Document::default();
+        document.title = Some("Reproducible".into());
+        document.pages.push(Page {
+            frame: Frame::hard(Size::splat(Abs::pt(100.0))),
+            numbering: None,
+            number: 1,
+        });
+
+        // With a pinned identifier and date, the output is byte-identical.
+        let timestamp = Datetime::from_ymd_hms(2024, 1, 2, 3, 4, 5);
+        let export = || {
+            crate::pdf(
+                &document,
+                Smart::Custom("test"),
+                timestamp,
+                None,
+                PdfStandard::V_1_7,
+            )
+            .unwrap()
+        };
+        let first = export();
+        assert_eq!(first, export());
+
+        let text = String::from_utf8_lossy(&first);
+        assert!(text.contains("/Title (Reproducible)"));
+        assert!(text.contains("/CreationDate (D:20240102030405"));
+        assert!(!text.contains("/OutputIntents"));
+    }
+
+    #[test]
+    fn test_pdf_output_intent() {
+        let size = Size::splat(Abs::pt(100.0));
+        let export = |colored: bool| {
+            let mut frame = Frame::hard(size);
+            if colored {
+                let shape = Geometry::Rect(size).filled(Color::RED.into());
+                frame.push(Point::zero(), FrameItem::Shape(shape, Span::detached()));
+            }
+            let mut document = Document::default();
+            document.pages.push(Page { frame, numbering: None, number: 1 });
+            let pdf = crate::pdf(&document, Smart::Auto, None, None, PdfStandard::A_2b)
+                .unwrap();
+            String::from_utf8_lossy(&pdf).into_owned()
+        };
+
+        // The output intent has an sRGB profile of its own if no content uses
+        // sRGB, and shares the color space's profile otherwise.
+        for colored in [false, true] {
+            let text = export(colored);
+            assert_eq!(text.matches("/OutputIntents").count(), 1);
+            assert_eq!(text.matches("/N 3").count(), 1);
+        }
+    }
+}
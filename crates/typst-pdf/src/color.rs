@@ -1,5 +1,6 @@
 use once_cell::sync::Lazy;
 use pdf_writer::{types::DeviceNSubtype, writers, Chunk, Dict, Filter, Name, Ref};
+use typst::diag::SourceResult;
 use typst::visualize::{Color, ColorSpace, Paint};
 
 use crate::{content, deflate, PdfChunk, Renumber, WithResources};
@@ -16,7 +17,7 @@ const OKLAB_A: Name<'static> = Name(b"A");
 const OKLAB_B: Name<'static> = Name(b"B");
 
 // The ICC profiles.
-static SRGB_ICC_DEFLATED: Lazy<Vec<u8>> =
+pub static SRGB_ICC_DEFLATED: Lazy<Vec<u8>> =
     Lazy::new(|| deflate(typst_assets::icc::S_RGB_V4));
 static GRAY_ICC_DEFLATED: Lazy<Vec<u8>> =
     Lazy::new(|| deflate(typst_assets::icc::S_GREY_V4));
@@ -156,6 +157,13 @@ pub struct ColorFunctionRefs {
     d65_gray: Option<Ref>,
 }
 
+impl ColorFunctionRefs {
+    /// The reference of the sRGB ICC profile, if the sRGB color space is used.
+    pub fn srgb(&self) -> Option<Ref> {
+        self.srgb
+    }
+}
+
 impl Renumber for ColorFunctionRefs {
     fn renumber(&mut self, offset: i32) {
         if let Some(r) = &mut self.oklab {
@@ -173,13 +181,15 @@ impl Renumber for ColorFunctionRefs {
 /// Allocate all necessary [`ColorFunctionRefs`].
 pub fn alloc_color_functions_refs(
     context: &WithResources,
-) -> (PdfChunk, ColorFunctionRefs) {
+) -> SourceResult<(PdfChunk, ColorFunctionRefs)> {
     let mut chunk = PdfChunk::new();
     let mut used_color_spaces = ColorSpaces::default();
 
     context.resources.traverse(&mut |r| {
         used_color_spaces.merge(&r.colors);
-    });
+
+        Ok(())
+    })?;
 
     let refs = ColorFunctionRefs {
         oklab: if used_color_spaces.use_oklab { Some(chunk.alloc()) } else { None },
@@ -187,7 +197,7 @@ pub fn alloc_color_functions_refs(
         d65_gray: if used_color_spaces.use_d65_gray { Some(chunk.alloc()) } else { None },
     };
 
-    (chunk, refs)
+    Ok((chunk, refs))
 }
 
 /// This function removes comments, line spaces and carriage returns from a
@@ -13,6 +13,7 @@ use pdf_writer::Filter;
 use pdf_writer::{types::UnicodeCmap, Finish, Name, Rect, Ref};
 use ttf_parser::name_id;
 
+use typst::diag::SourceResult;
 use typst::layout::Em;
 use typst::text::{color::frame_for_glyph, Font};
 
@@ -30,12 +31,12 @@ use crate::{
 /// instructions.
 pub fn write_color_fonts(
     context: &WithGlobalRefs,
-) -> (PdfChunk, HashMap<ColorFontSlice, Ref>) {
+) -> SourceResult<(PdfChunk, HashMap<ColorFontSlice, Ref>)> {
     let mut out = HashMap::new();
     let mut chunk = PdfChunk::new();
     context.resources.traverse(&mut |resources: &Resources| {
         let Some(color_fonts) = &resources.color_fonts else {
-            return;
+            return Ok(());
         };
 
         for (color_font, font_slice) in color_fonts.iter() {
@@ -148,9 +149,11 @@ pub fn write_color_fonts(
 
             out.insert(font_slice, subfont_id);
         }
-    });
 
-    (chunk, out)
+        Ok(())
+    })?;
+
+    Ok((chunk, out))
 }
 
 /// A mapping between `Font`s and all the corresponding `ColorFont`s.
@@ -16,7 +16,8 @@ use typst::model::Destination;
 use typst::text::{color::is_color_glyph, Font, TextItem, TextItemView};
 use typst::utils::{Deferred, Numeric, SliceExt};
 use typst::visualize::{
-    FixedStroke, Geometry, Image, LineCap, LineJoin, Paint, Path, PathItem, Shape,
+    FillRule, FixedStroke, Geometry, Image, LineCap, LineJoin, Paint, Path, PathItem,
+    Shape,
 };
 
 use crate::color_font::ColorFontMap;
@@ -73,7 +74,7 @@ pub struct Encoded {
     /// Whether the content opacities.
     pub uses_opacities: bool,
     /// Links in the PDF coordinate system.
-    pub links: Vec<(Destination, Rect)>,
+    pub links: Vec<(Destination, Rect, Option<[f32; 8]>)>,
 }
 
 /// An exporter for a single PDF content stream.
@@ -95,7 +96,7 @@ pub struct Builder<'a, R = ()> {
     /// Wheter any stroke or fill was not totally opaque.
     uses_opacities: bool,
     /// All clickable links that are present in this content.
-    links: Vec<(Destination, Rect)>,
+    links: Vec<(Destination, Rect, Option<[f32; 8]>)>,
 }
 
 impl<'a, R> Builder<'a, R> {
@@ -631,9 +632,15 @@ fn write_shape(ctx: &mut Builder, pos: Point, shape: &Shape) {
 
     match (&shape.fill, stroke) {
         (None, None) => unreachable!(),
-        (Some(_), None) => ctx.content.fill_nonzero(),
+        (Some(_), None) => match shape.fill_rule {
+            FillRule::NonZero => ctx.content.fill_nonzero(),
+            FillRule::EvenOdd => ctx.content.fill_even_odd(),
+        },
         (None, Some(_)) => ctx.content.stroke(),
-        (Some(_), Some(_)) => ctx.content.fill_nonzero_and_stroke(),
+        (Some(_), Some(_)) => match shape.fill_rule {
+            FillRule::NonZero => ctx.content.fill_nonzero_and_stroke(),
+            FillRule::EvenOdd => ctx.content.fill_even_odd_and_stroke(),
+        },
     };
 }
 
@@ -696,19 +703,31 @@ fn write_image(ctx: &mut Builder, x: f32, y: f32, image: &Image, size: Size) {
 
 /// Save a link for later writing in the annotations dictionary.
 fn write_link(ctx: &mut Builder, pos: Point, dest: &Destination, size: Size) {
+    let (rect, quad) = link_area(pos, size, ctx.state.transform);
+    ctx.links.push((dest.clone(), rect, quad));
+}
+
+/// Compute the annotation rectangle of a link with the given transform.
+///
+/// If the link is rotated or skewed, the rectangle is its bounding box and
+/// the exact area is additionally returned as quad points (counterclockwise,
+/// starting at the bottom left).
+fn link_area(pos: Point, size: Size, ts: Transform) -> (Rect, Option<[f32; 8]>) {
     let mut min_x = Abs::inf();
     let mut min_y = Abs::inf();
     let mut max_x = -Abs::inf();
     let mut max_y = -Abs::inf();
 
     // Compute the bounding box of the transformed link.
-    for point in [
-        pos,
-        pos + Point::with_x(size.x),
+    let corners = [
         pos + Point::with_y(size.y),
         pos + size.to_point(),
-    ] {
-        let t = point.transform(ctx.state.transform);
+        pos + Point::with_x(size.x),
+        pos,
+    ]
+    .map(|point| point.transform(ts));
+
+    for t in corners {
         min_x.set_min(t.x);
         min_y.set_min(t.y);
         max_x.set_max(t.x);
@@ -721,7 +740,13 @@ fn write_link(ctx: &mut Builder, pos: Point, dest: &Destination, size: Size) {
     let y2 = min_y.to_f32();
     let rect = Rect::new(x1, y1, x2, y2);
 
-    ctx.links.push((dest.clone(), rect));
+    let axis_aligned = ts.kx.is_zero() && ts.ky.is_zero();
+    let quad = (!axis_aligned).then(|| {
+        let [a, b, c, d] = corners;
+        [a.x, a.y, b.x, b.y, c.x, c.y, d.x, d.y].map(|v| v.to_f32())
+    });
+
+    (rect, quad)
 }
 
 fn to_pdf_line_cap(cap: LineCap) -> LineCapStyle {
@@ -739,3 +764,32 @@ fn to_pdf_line_join(join: LineJoin) -> LineJoinStyle {
         LineJoin::Bevel => LineJoinStyle::BevelJoin,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use typst::layout::Angle;
+
+    use super::*;
+
+    #[test]
+    fn test_link_area() {
+        let pos = Point::new(Abs::pt(5.0), Abs::pt(10.0));
+        let size = Size::new(Abs::pt(20.0), Abs::pt(10.0));
+        let (rect, quad) = link_area(pos, size, Transform::identity());
+        assert_eq!((rect.x1, rect.y1, rect.x2, rect.y2), (5.0, 20.0, 25.0, 10.0));
+        assert_eq!(quad, None);
+
+        // A rotated link gets its bounding box and the exact quad.
+        let ts = Transform::rotate(Angle::deg(90.0));
+        let (rect, quad) = link_area(Point::zero(), size, ts);
+        let round = |v: f32| v.round();
+        assert_eq!(
+            [rect.x1, rect.y1, rect.x2, rect.y2].map(round),
+            [-10.0, 20.0, 0.0, 0.0],
+        );
+        assert_eq!(
+            quad.unwrap().map(round),
+            [-10.0, 0.0, -10.0, 20.0, 0.0, 20.0, 0.0, 0.0],
+        );
+    }
+}
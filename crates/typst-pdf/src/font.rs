@@ -10,6 +10,8 @@ use pdf_writer::{
 };
 use subsetter::GlyphRemapper;
 use ttf_parser::{name_id, GlyphId, Tag};
+use typst::diag::{bail, SourceResult};
+use typst::syntax::Span;
 use typst::text::Font;
 use typst::utils::SliceExt;
 use unicode_properties::{GeneralCategory, UnicodeGeneralCategory};
@@ -27,7 +29,9 @@ pub(crate) const SYSTEM_INFO: SystemInfo = SystemInfo {
 
 /// Embed all used fonts into the PDF.
 #[typst_macros::time(name = "write fonts")]
-pub fn write_fonts(context: &WithGlobalRefs) -> (PdfChunk, HashMap<Font, Ref>) {
+pub fn write_fonts(
+    context: &WithGlobalRefs,
+) -> SourceResult<(PdfChunk, HashMap<Font, Ref>)> {
     let mut chunk = PdfChunk::new();
     let mut out = HashMap::new();
     context.resources.traverse(&mut |resources| {
@@ -61,6 +65,16 @@ pub fn write_fonts(context: &WithGlobalRefs) -> (PdfChunk, HashMap<Font, Ref>) {
                 .find_name(name_id::POST_SCRIPT_NAME)
                 .unwrap_or_else(|| "unknown".to_string());
 
+            // The content streams already refer to the glyph ids of the
+            // subset, so there is nothing to fall back to if subsetting fails.
+            let Some(subset) = subset_font(font, glyph_remapper) else {
+                bail!(
+                    Span::detached(),
+                    "failed to subset font {postscript_name}";
+                    hint: "the font may use tables that are not supported yet"
+                );
+            };
+
             let subset_tag = subset_tag(glyph_set);
             let base_font = eco_format!("{subset_tag}+{postscript_name}");
             let base_font_type0 = if is_cff {
@@ -119,7 +133,6 @@ pub fn write_fonts(context: &WithGlobalRefs) -> (PdfChunk, HashMap<Font, Ref>) {
             let cmap = create_cmap(glyph_set, glyph_remapper);
             chunk.cmap(cmap_ref, &cmap).filter(Filter::FlateDecode);
 
-            let subset = subset_font(font, glyph_remapper);
             let mut stream = chunk.stream(data_ref, &subset);
             stream.filter(Filter::FlateDecode);
             if is_cff {
@@ -135,9 +148,11 @@ pub fn write_fonts(context: &WithGlobalRefs) -> (PdfChunk, HashMap<Font, Ref>) {
                 font_descriptor.font_file2(data_ref);
             }
         }
-    });
 
-    (chunk, out)
+        Ok(())
+    })?;
+
+    Ok((chunk, out))
 }
 
 /// Writes a FontDescriptor dictionary.
@@ -194,23 +209,23 @@ pub fn write_font_descriptor<'a>(
 /// - For a font with TrueType outlines, this produces the whole OpenType font.
 /// - For a font with CFF outlines, this produces just the CFF font program.
 ///
-/// In both cases, this returns the already compressed data.
+/// In both cases, this returns the already compressed data. Returns `None` if
+/// the font's tables are not supported by the subsetter.
 #[comemo::memoize]
 #[typst_macros::time(name = "subset font")]
-fn subset_font(font: &Font, glyph_remapper: &GlyphRemapper) -> Arc<Vec<u8>> {
+fn subset_font(font: &Font, glyph_remapper: &GlyphRemapper) -> Option<Arc<Vec<u8>>> {
     let data = font.data();
-    // TODO: Fail export instead of unwrapping once export diagnoistics exist.
-    let subsetted = subsetter::subset(data, font.index(), glyph_remapper).unwrap();
+    let subsetted = subsetter::subset(data, font.index(), glyph_remapper).ok()?;
 
     let mut data = subsetted.as_ref();
 
     // Extract the standalone CFF font program if applicable.
-    let raw = ttf_parser::RawFace::parse(data, 0).unwrap();
+    let raw = ttf_parser::RawFace::parse(data, 0).ok()?;
     if let Some(cff) = raw.table(CFF) {
         data = cff;
     }
 
-    Arc::new(deflate(data))
+    Some(Arc::new(deflate(data)))
 }
 
 /// Produce a unique 6 letter tag for a glyph set.
@@ -276,3 +291,91 @@ fn create_cmap(
     }
     Arc::new(deflate(&cmap.finish()))
 }
+
+#[cfg(test)]
+mod tests {
+    use typst::diag::{FileError, FileResult};
+    use typst::foundations::{Bytes, Datetime, Smart};
+    use typst::syntax::{FileId, Source};
+    use typst::text::FontBook;
+    use typst::utils::LazyHash;
+    use typst::{Library, World};
+
+    use super::*;
+    use crate::PdfStandard;
+
+    /// A world with the bundled and the test fonts.
+    struct FontWorld {
+        main: Source,
+        library: LazyHash<Library>,
+        book: LazyHash<FontBook>,
+        fonts: Vec<Font>,
+    }
+
+    impl World for FontWorld {
+        fn library(&self) -> &LazyHash<Library> {
+            &self.library
+        }
+
+        fn book(&self) -> &LazyHash<FontBook> {
+            &self.book
+        }
+
+        fn main(&self) -> Source {
+            self.main.clone()
+        }
+
+        fn source(&self, id: FileId) -> FileResult<Source> {
+            if id == self.main.id() {
+                Ok(self.main.clone())
+            } else {
+                Err(FileError::NotFound(id.vpath().as_rootless_path().into()))
+            }
+        }
+
+        fn file(&self, id: FileId) -> FileResult<Bytes> {
+            Err(FileError::NotFound(id.vpath().as_rootless_path().into()))
+        }
+
+        fn font(&self, index: usize) -> Option<Font> {
+            self.fonts.get(index).cloned()
+        }
+
+        fn today(&self, _: Option<i64>) -> Option<Datetime> {
+            None
+        }
+    }
+
+    #[test]
+    fn test_subset_cjk_document() {
+        let fonts: Vec<_> = typst_assets::fonts()
+            .chain(typst_dev_assets::fonts())
+            .flat_map(|data| Font::iter(Bytes::from_static(data)))
+            .collect();
+        let cjk = fonts
+            .iter()
+            .find(|font| font.info().family == "Noto Serif CJK SC")
+            .cloned()
+            .unwrap();
+        assert!(cjk.ttf().raw_face().table(CFF).is_some());
+
+        let world = FontWorld {
+            main: Source::detached("#set text(font: \"Noto Serif CJK SC\")\n你好"),
+            library: LazyHash::new(Library::default()),
+            book: LazyHash::new(FontBook::from_fonts(&fonts)),
+            fonts,
+        };
+        let document = typst::compile(&world).output.unwrap();
+        let pdf =
+            crate::pdf(&document, Smart::Auto, None, None, PdfStandard::V_1_7).unwrap();
+
+        // Two glyphs need only a fraction of the whole font.
+        let full = deflate(cjk.data());
+        assert!(
+            pdf.len() * 20 < full.len(),
+            "PDF is too large ({} vs. {} bytes for the font)",
+            pdf.len(),
+            full.len(),
+        );
+    }
+}
@@ -20,6 +20,7 @@ use std::ops::{Deref, DerefMut};
 
 use base64::Engine;
 use pdf_writer::{Chunk, Pdf, Ref};
+use typst::diag::SourceResult;
 use typst::foundations::{Datetime, Smart};
 use typst::layout::{Abs, Em, PageRanges, Transform};
 use typst::model::Document;
@@ -63,32 +64,56 @@ use crate::resources::{
 ///
 /// The `page_ranges` option specifies which ranges of pages should be exported
 /// in the PDF. When `None`, all pages should be exported.
+///
+/// The `standard` defines which PDF standard the output conforms to. See
+/// [`PdfStandard`] for what each one entails.
+///
+/// Fails if a resource cannot be embedded into the PDF, for instance, because
+/// a font cannot be subset.
 #[typst_macros::time(name = "pdf")]
 pub fn pdf(
     document: &Document,
     ident: Smart<&str>,
     timestamp: Option<Datetime>,
     page_ranges: Option<PageRanges>,
-) -> Vec<u8> {
-    PdfBuilder::new(document, page_ranges)
-        .phase(|builder| builder.run(traverse_pages))
-        .phase(|builder| GlobalRefs {
-            color_functions: builder.run(alloc_color_functions_refs),
-            pages: builder.run(alloc_page_refs),
-            resources: builder.run(alloc_resources_refs),
-        })
-        .phase(|builder| References {
-            named_destinations: builder.run(write_named_destinations),
-            fonts: builder.run(write_fonts),
-            color_fonts: builder.run(write_color_fonts),
-            images: builder.run(write_images),
-            gradients: builder.run(write_gradients),
-            patterns: builder.run(write_patterns),
-            ext_gs: builder.run(write_graphic_states),
-        })
-        .phase(|builder| builder.run(write_page_tree))
-        .phase(|builder| builder.run(write_resource_dictionaries))
-        .export_with(ident, timestamp, write_catalog)
+    standard: PdfStandard,
+) -> SourceResult<Vec<u8>> {
+    Ok(PdfBuilder::new(document, page_ranges)
+        .phase(|builder| builder.run(traverse_pages))?
+        .phase(|builder| {
+            Ok(GlobalRefs {
+                color_functions: builder.run(alloc_color_functions_refs)?,
+                pages: builder.run(alloc_page_refs)?,
+                resources: builder.run(alloc_resources_refs)?,
+            })
+        })?
+        .phase(|builder| {
+            Ok(References {
+                named_destinations: builder.run(write_named_destinations)?,
+                fonts: builder.run(write_fonts)?,
+                color_fonts: builder.run(write_color_fonts)?,
+                images: builder.run(write_images)?,
+                gradients: builder.run(write_gradients)?,
+                patterns: builder.run(write_patterns)?,
+                ext_gs: builder.run(write_graphic_states)?,
+            })
+        })?
+        .phase(|builder| builder.run(write_page_tree))?
+        .phase(|builder| builder.run(write_resource_dictionaries))?
+        .export_with(ident, timestamp, standard, write_catalog))
+}
+
+/// A PDF standard that an exported PDF conforms to.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Hash)]
+#[allow(non_camel_case_types)]
+pub enum PdfStandard {
+    /// PDF 1.7.
+    #[default]
+    V_1_7,
+    /// PDF/A-2b, which is meant for archival. The PDF declares sRGB as its
+    /// output intent. This does not check that the document conforms to
+    /// PDF/A otherwise.
+    A_2b,
 }
 
 /// A struct to build a PDF following a fixed succession of phases.
@@ -295,31 +320,31 @@ impl<'a> PdfBuilder<WithDocument<'a>> {
 
 impl<S> PdfBuilder<S> {
     /// Start a new phase, and save its output in the global state.
-    fn phase<NS, B, O>(mut self, builder: B) -> PdfBuilder<NS>
+    fn phase<NS, B, O>(mut self, builder: B) -> SourceResult<PdfBuilder<NS>>
     where
         // New state
         NS: From<(S, O)>,
         // Builder
-        B: Fn(&mut Self) -> O,
+        B: Fn(&mut Self) -> SourceResult<O>,
     {
-        let output = builder(&mut self);
-        PdfBuilder {
+        let output = builder(&mut self)?;
+        Ok(PdfBuilder {
             state: NS::from((self.state, output)),
             alloc: self.alloc,
             pdf: self.pdf,
-        }
+        })
     }
 
     /// Runs a step with the current state, merge its output in the PDF file,
     /// and renumber any references it returned.
-    fn run<P, O>(&mut self, process: P) -> O
+    fn run<P, O>(&mut self, process: P) -> SourceResult<O>
     where
         // Process
-        P: Fn(&S) -> (PdfChunk, O),
+        P: Fn(&S) -> SourceResult<(PdfChunk, O)>,
         // Output
         O: Renumber,
     {
-        let (chunk, mut output) = process(&self.state);
+        let (chunk, mut output) = process(&self.state)?;
         // Allocate a final reference for each temporary one
         let allocated = chunk.alloc.get() - TEMPORARY_REFS_START;
         let offset = TEMPORARY_REFS_START - self.alloc.get();
@@ -336,7 +361,7 @@ impl<S> PdfBuilder<S> {
 
         self.alloc = Ref::new(self.alloc.get() + allocated);
 
-        output
+        Ok(output)
     }
 
     /// Finalize the PDF export and returns the buffer representing the
@@ -345,12 +370,13 @@ impl<S> PdfBuilder<S> {
         mut self,
         ident: Smart<&str>,
         timestamp: Option<Datetime>,
+        standard: PdfStandard,
         process: P,
     ) -> Vec<u8>
     where
-        P: Fn(S, Smart<&str>, Option<Datetime>, &mut Pdf, &mut Ref),
+        P: Fn(S, Smart<&str>, Option<Datetime>, PdfStandard, &mut Pdf, &mut Ref),
     {
-        process(self.state, ident, timestamp, &mut self.pdf, &mut self.alloc);
+        process(self.state, ident, timestamp, standard, &mut self.pdf, &mut self.alloc);
         self.pdf.finish()
     }
 }
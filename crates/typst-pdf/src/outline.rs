@@ -14,6 +14,56 @@ pub(crate) fn write_outline(
     alloc: &mut Ref,
     ctx: &WithEverything,
 ) -> Option<Ref> {
+    let elements = ctx.document.introspector.query(&HeadingElem::elem().select());
+    let headings = elements
+        .iter()
+        .filter(|elem| {
+            // Don't bookmark headings in non-exported pages
+            ctx.exported_pages.as_ref().map_or(true, |page_ranges| {
+                page_ranges.includes_page(
+                    ctx.document.introspector.page(elem.location().unwrap()),
+                )
+            })
+        })
+        .map(|elem| elem.to_packed::<HeadingElem>().unwrap());
+
+    let tree = build_tree(headings);
+    if tree.is_empty() {
+        return None;
+    }
+
+    let root_id = alloc.bump();
+    let start_ref = *alloc;
+    let len = tree.len();
+
+    let mut prev_ref = None;
+    for (i, node) in tree.iter().enumerate() {
+        prev_ref = Some(write_outline_item(
+            ctx,
+            chunk,
+            alloc,
+            node,
+            root_id,
+            prev_ref,
+            i + 1 == len,
+        ));
+    }
+
+    chunk
+        .outline(root_id)
+        .first(start_ref)
+        .last(Ref::new(
+            alloc.get() - tree.last().map(|child| child.len() as i32).unwrap_or(1),
+        ))
+        .count(tree.len() as i32);
+
+    Some(root_id)
+}
+
+/// Arrange the headings into the tree of bookmarks, following their levels.
+fn build_tree<'a>(
+    headings: impl IntoIterator<Item = &'a Packed<HeadingElem>>,
+) -> Vec<HeadingNode<'a>> {
     let mut tree: Vec<HeadingNode> = vec![];
 
     // Stores the level of the topmost skipped ancestor of the next bookmarked
@@ -22,19 +72,8 @@ pub(crate) fn write_outline(
     // Therefore, its next descendant must be added at its level, which is
     // enforced in the manner shown below.
     let mut last_skipped_level = None;
-    let elements = ctx.document.introspector.query(&HeadingElem::elem().select());
 
-    for elem in elements.iter() {
-        if let Some(page_ranges) = &ctx.exported_pages {
-            if !page_ranges
-                .includes_page(ctx.document.introspector.page(elem.location().unwrap()))
-            {
-                // Don't bookmark headings in non-exported pages
-                continue;
-            }
-        }
-
-        let heading = elem.to_packed::<HeadingElem>().unwrap();
+    for heading in headings {
         let leaf = HeadingNode::leaf(heading);
 
         if leaf.bookmarked {
@@ -96,36 +135,7 @@ pub(crate) fn write_outline(
         }
     }
 
-    if tree.is_empty() {
-        return None;
-    }
-
-    let root_id = alloc.bump();
-    let start_ref = *alloc;
-    let len = tree.len();
-
-    let mut prev_ref = None;
-    for (i, node) in tree.iter().enumerate() {
-        prev_ref = Some(write_outline_item(
-            ctx,
-            chunk,
-            alloc,
-            node,
-            root_id,
-            prev_ref,
-            i + 1 == len,
-        ));
-    }
-
-    chunk
-        .outline(root_id)
-        .first(start_ref)
-        .last(Ref::new(
-            alloc.get() - tree.last().map(|child| child.len() as i32).unwrap_or(1),
-        ))
-        .count(tree.len() as i32);
-
-    Some(root_id)
+    tree
 }
 
 /// A heading in the outline panel.
@@ -221,3 +231,54 @@ fn write_outline_item(
 
     id
 }
+
+#[cfg(test)]
+mod tests {
+    use typst::foundations::Smart;
+    use typst::text::TextElem;
+
+    use super::*;
+
+    fn heading(title: &str, level: usize, bookmarked: bool) -> Packed<HeadingElem> {
+        Packed::new(
+            HeadingElem::new(TextElem::packed(title))
+                .with_level(Smart::Custom(NonZeroUsize::new(level).unwrap()))
+                .with_bookmarked(Smart::Custom(bookmarked)),
+        )
+    }
+
+    fn render(nodes: &[HeadingNode]) -> String {
+        nodes
+            .iter()
+            .map(|node| {
+                let title = node.element.body().plain_text();
+                if node.children.is_empty() {
+                    title.to_string()
+                } else {
+                    format!("{title}({})", render(&node.children))
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    #[test]
+    fn test_outline_tree() {
+        let headings = [
+            heading("A", 1, true),
+            heading("B", 3, true),
+            heading("C", 1, true),
+            heading("D", 2, false),
+            heading("E", 3, true),
+            heading("F", 2, true),
+            heading("G", 3, true),
+            heading("H", 1, false),
+            heading("I", 1, true),
+        ];
+
+        // Skipped levels nest below the closest bookmarked heading and the
+        // children of headings that are not bookmarked take their place.
+        let tree = build_tree(&headings);
+        assert_eq!(render(&tree), "A(B) C(E F(G)) I");
+    }
+}
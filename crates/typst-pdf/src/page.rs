@@ -6,6 +6,7 @@ use pdf_writer::{
     types::{ActionType, AnnotationFlags, AnnotationType, NumberingStyle},
     Filter, Finish, Name, Rect, Ref, Str,
 };
+use typst::diag::SourceResult;
 use typst::foundations::Label;
 use typst::introspection::Location;
 use typst::layout::{Abs, Frame};
@@ -19,7 +20,7 @@ use crate::{font::improve_glyph_sets, Resources};
 #[typst_macros::time(name = "construct pages")]
 pub fn traverse_pages(
     state: &WithDocument,
-) -> (PdfChunk, (Vec<Option<EncodedPage>>, Resources<()>)) {
+) -> SourceResult<(PdfChunk, (Vec<Option<EncodedPage>>, Resources<()>))> {
     let mut resources = Resources::default();
     let mut pages = Vec::with_capacity(state.document.pages.len());
     let mut skipped_pages = 0;
@@ -55,7 +56,7 @@ pub fn traverse_pages(
     improve_glyph_sets(&mut resources.glyph_sets);
     improve_glyph_sets(&mut resources.color_glyph_sets);
 
-    (PdfChunk::new(), (pages, resources))
+    Ok((PdfChunk::new(), (pages, resources)))
 }
 
 /// Construct a page object.
@@ -67,18 +68,20 @@ fn construct_page(out: &mut Resources<()>, frame: &Frame) -> EncodedPage {
 }
 
 /// Allocate a reference for each exported page.
-pub fn alloc_page_refs(context: &WithResources) -> (PdfChunk, Vec<Option<Ref>>) {
+pub fn alloc_page_refs(
+    context: &WithResources,
+) -> SourceResult<(PdfChunk, Vec<Option<Ref>>)> {
     let mut chunk = PdfChunk::new();
     let page_refs = context
         .pages
         .iter()
         .map(|p| p.as_ref().map(|_| chunk.alloc()))
         .collect();
-    (chunk, page_refs)
+    Ok((chunk, page_refs))
 }
 
 /// Write the page tree.
-pub fn write_page_tree(ctx: &WithRefs) -> (PdfChunk, Ref) {
+pub fn write_page_tree(ctx: &WithRefs) -> SourceResult<(PdfChunk, Ref)> {
     let mut chunk = PdfChunk::new();
     let page_tree_ref = chunk.alloc.bump();
 
@@ -99,7 +102,7 @@ pub fn write_page_tree(ctx: &WithRefs) -> (PdfChunk, Ref) {
         .count(ctx.pages.len() as i32)
         .kids(ctx.globals.pages.iter().filter_map(Option::as_ref).copied());
 
-    (chunk, page_tree_ref)
+    Ok((chunk, page_tree_ref))
 }
 
 /// Write a page tree node.
@@ -117,13 +120,16 @@ fn write_page(
     };
 
     let mut annotations = Vec::with_capacity(page.content.links.len());
-    for (dest, rect) in &page.content.links {
+    for (dest, rect, quad) in &page.content.links {
         let id = chunk.alloc();
         annotations.push(id);
 
         let mut annotation = chunk.annotation(id);
         annotation.subtype(AnnotationType::Link).rect(*rect);
         annotation.border(0.0, 0.0, 0.0, None).flags(AnnotationFlags::PRINT);
+        if let Some(quad) = quad {
+            annotation.quad_points(quad.iter().copied());
+        }
 
         let pos = match dest {
             Destination::Url(uri) => {
@@ -6,6 +6,7 @@ use pdf_writer::{
     Filter, Name, Rect, Ref,
 };
 
+use typst::diag::SourceResult;
 use typst::layout::{Abs, Ratio, Transform};
 use typst::utils::Numeric;
 use typst::visualize::{Pattern, RelativeTo};
@@ -16,12 +17,14 @@ use crate::{transform_to_array, PdfChunk};
 
 /// Writes the actual patterns (tiling patterns) to the PDF.
 /// This is performed once after writing all pages.
-pub fn write_patterns(context: &WithGlobalRefs) -> (PdfChunk, HashMap<PdfPattern, Ref>) {
+pub fn write_patterns(
+    context: &WithGlobalRefs,
+) -> SourceResult<(PdfChunk, HashMap<PdfPattern, Ref>)> {
     let mut chunk = PdfChunk::new();
     let mut out = HashMap::new();
     context.resources.traverse(&mut |resources| {
         let Some(patterns) = &resources.patterns else {
-            return;
+            return Ok(());
         };
 
         for pdf_pattern in patterns.remapper.items() {
@@ -60,9 +63,11 @@ pub fn write_patterns(context: &WithGlobalRefs) -> (PdfChunk, HashMap<PdfPattern
                 ))
                 .filter(Filter::FlateDecode);
         }
-    });
 
-    (chunk, out)
+        Ok(())
+    })?;
+
+    Ok((chunk, out))
 }
 
 /// A pattern and its transform.
@@ -12,6 +12,7 @@ use std::hash::Hash;
 use ecow::{eco_format, EcoString};
 use pdf_writer::{Dict, Finish, Name, Ref};
 use subsetter::GlyphRemapper;
+use typst::diag::SourceResult;
 use typst::text::Lang;
 use typst::{text::Font, utils::Deferred, visualize::Image};
 
@@ -152,19 +153,20 @@ impl Resources<()> {
 }
 
 impl<R> Resources<R> {
-    /// Run a function on this resource dictionary and all
-    /// of its sub-resources.
-    pub fn traverse<P>(&self, process: &mut P)
+    /// Run a function on this resource dictionary and all of its
+    /// sub-resources, stopping at the first error.
+    pub fn traverse<P>(&self, process: &mut P) -> SourceResult<()>
     where
-        P: FnMut(&Self),
+        P: FnMut(&Self) -> SourceResult<()>,
     {
-        process(self);
+        process(self)?;
         if let Some(color_fonts) = &self.color_fonts {
-            color_fonts.resources.traverse(process)
+            color_fonts.resources.traverse(process)?;
         }
         if let Some(patterns) = &self.patterns {
-            patterns.resources.traverse(process)
+            patterns.resources.traverse(process)?;
         }
+        Ok(())
     }
 }
 
@@ -191,7 +193,9 @@ impl Renumber for ResourcesRefs {
 }
 
 /// Allocate references for all resource dictionaries.
-pub fn alloc_resources_refs(context: &WithResources) -> (PdfChunk, ResourcesRefs) {
+pub fn alloc_resources_refs(
+    context: &WithResources,
+) -> SourceResult<(PdfChunk, ResourcesRefs)> {
     let mut chunk = PdfChunk::new();
     /// Recursively explore resource dictionaries and assign them references.
     fn refs_for(resources: &Resources<()>, chunk: &mut PdfChunk) -> ResourcesRefs {
@@ -209,7 +213,7 @@ pub fn alloc_resources_refs(context: &WithResources) -> (PdfChunk, ResourcesRefs
     }
 
     let refs = refs_for(&context.resources, &mut chunk);
-    (chunk, refs)
+    Ok((chunk, refs))
 }
 
 /// Write the resource dictionaries that will be referenced by all pages.
@@ -219,7 +223,7 @@ pub fn alloc_resources_refs(context: &WithResources) -> (PdfChunk, ResourcesRefs
 /// feature breaks PDF merging with Apple Preview.
 ///
 /// Also write resource dictionaries for Type3 fonts and patterns.
-pub fn write_resource_dictionaries(ctx: &WithEverything) -> (PdfChunk, ()) {
+pub fn write_resource_dictionaries(ctx: &WithEverything) -> SourceResult<(PdfChunk, ())> {
     let mut chunk = PdfChunk::new();
     let mut used_color_spaces = ColorSpaces::default();
 
@@ -282,11 +286,13 @@ pub fn write_resource_dictionaries(ctx: &WithEverything) -> (PdfChunk, ()) {
         resources
             .colors
             .write_color_spaces(color_spaces, &ctx.globals.color_functions);
-    });
+
+        Ok(())
+    })?;
 
     used_color_spaces.write_functions(&mut chunk, &ctx.globals.color_functions);
 
-    (chunk, ())
+    Ok((chunk, ()))
 }
 
 /// Assigns new, consecutive PDF-internal indices to items.
@@ -172,7 +172,7 @@ fn render_group(canvas: &mut sk::Pixmap, state: State, pos: Point, group: &Group
     let sk_transform = to_sk_transform(&group.transform);
     let state = match group.frame.kind() {
         FrameKind::Soft => state.pre_translate(pos).pre_concat(sk_transform),
-        FrameKind::Hard => state
+        FrameKind::Hard | FrameKind::Container => state
             .pre_translate(pos)
             .pre_concat(sk_transform)
             .pre_concat_container(
@@ -248,3 +248,145 @@ impl AbsExt for Abs {
         self.to_pt() as f32
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use typst::layout::Ratio;
+    use typst::syntax::Span;
+    use typst::visualize::{FillRule, Geometry, Path, Shape};
+
+    use super::*;
+
+    /// A red square of the given side length in points.
+    fn square(side: f64) -> FrameItem {
+        let size = Size::splat(Abs::pt(side));
+        FrameItem::Shape(Geometry::Rect(size).filled(Color::RED.into()), Span::detached())
+    }
+
+    /// Like [`square`], but as a path, which is anti-aliased.
+    fn square_path(side: f64) -> FrameItem {
+        let path = Path::rect(Size::splat(Abs::pt(side)));
+        FrameItem::Shape(Geometry::Path(path).filled(Color::RED.into()), Span::detached())
+    }
+
+    /// Draws red rectangles, given in pixels, onto a white canvas directly
+    /// with `tiny-skia`.
+    fn reference(width: u32, height: u32, rects: &[(f32, f32, f32, f32)]) -> sk::Pixmap {
+        let mut canvas = sk::Pixmap::new(width, height).unwrap();
+        canvas.fill(paint::to_sk_color(Color::WHITE));
+        let mut paint = sk::Paint::default();
+        paint.set_color(paint::to_sk_color(Color::RED));
+        for &(x, y, w, h) in rects {
+            let rect = sk::Rect::from_xywh(x, y, w, h).unwrap();
+            canvas.fill_rect(rect, &paint, sk::Transform::identity(), None);
+        }
+        canvas
+    }
+
+    /// Asserts that two renders only differ by rounding.
+    #[track_caller]
+    fn assert_similar(render: &sk::Pixmap, reference: &sk::Pixmap) {
+        assert_eq!(
+            (render.width(), render.height()),
+            (reference.width(), reference.height())
+        );
+        let differing = render
+            .data()
+            .iter()
+            .zip(reference.data())
+            .filter(|(a, b)| a.abs_diff(**b) > 2)
+            .count();
+        assert_eq!(differing, 0);
+    }
+
+    #[test]
+    fn test_render_rect() {
+        let mut frame = Frame::hard(Size::splat(Abs::pt(10.0)));
+        frame.push(Point::splat(Abs::pt(3.0)), square(4.0));
+
+        let pixmap = render(&frame, 2.0, Color::WHITE);
+        assert_similar(&pixmap, &reference(20, 20, &[(6.0, 6.0, 8.0, 8.0)]));
+    }
+
+    #[test]
+    fn test_render_subpixel_position() {
+        // Half a pixel off the grid, the edges are blended half-and-half.
+        let mut frame = Frame::hard(Size::splat(Abs::pt(10.0)));
+        frame.push(Point::splat(Abs::pt(2.25)), square_path(4.0));
+
+        let pixmap = render(&frame, 2.0, Color::WHITE);
+        assert_similar(&pixmap, &reference(20, 20, &[(4.5, 4.5, 8.0, 8.0)]));
+    }
+
+    #[test]
+    fn test_render_transformed_and_clipped_group() {
+        // A scaled square.
+        let mut inner = Frame::soft(Size::splat(Abs::pt(2.0)));
+        inner.push(Point::zero(), square(2.0));
+        let mut scaled = GroupItem::new(inner);
+        scaled.transform = Transform::scale(Ratio::new(2.0), Ratio::new(3.0));
+
+        // A large square clipped to its smaller frame.
+        let size = Size::new(Abs::pt(3.0), Abs::pt(2.0));
+        let mut inner = Frame::hard(size);
+        inner.push(Point::splat(Abs::pt(-5.0)), square(20.0));
+        let mut clipped = GroupItem::new(inner);
+        clipped.clip_path = Some(Path::rect(size));
+
+        let mut frame = Frame::hard(Size::splat(Abs::pt(10.0)));
+        frame.push(Point::new(Abs::pt(1.0), Abs::pt(2.0)), FrameItem::Group(scaled));
+        frame.push(Point::new(Abs::pt(6.0), Abs::pt(7.0)), FrameItem::Group(clipped));
+
+        let pixmap = render(&frame, 1.0, Color::WHITE);
+        assert_similar(
+            &pixmap,
+            &reference(10, 10, &[(1.0, 2.0, 4.0, 6.0), (6.0, 7.0, 3.0, 2.0)]),
+        );
+    }
+
+    #[test]
+    fn test_render_fill_rule() {
+        // A square with a smaller square inside of it, both drawn clockwise.
+        let mut path = Path::new();
+        for (min, max) in [(0.0, 8.0), (2.0, 6.0)] {
+            path.move_to(Point::splat(Abs::pt(min)));
+            path.line_to(Point::new(Abs::pt(max), Abs::pt(min)));
+            path.line_to(Point::splat(Abs::pt(max)));
+            path.line_to(Point::new(Abs::pt(min), Abs::pt(max)));
+            path.close_path();
+        }
+
+        let draw = |fill_rule| {
+            let shape = Shape {
+                fill_rule,
+                ..Geometry::Path(path.clone()).filled(Color::RED.into())
+            };
+            let mut frame = Frame::hard(Size::splat(Abs::pt(10.0)));
+            frame.push(
+                Point::splat(Abs::pt(1.0)),
+                FrameItem::Shape(shape, Span::detached()),
+            );
+            render(&frame, 1.0, Color::WHITE)
+        };
+
+        // With the non-zero rule, the inner square is filled as well. With
+        // the even-odd rule, it is a hole.
+        assert_similar(
+            &draw(FillRule::NonZero),
+            &reference(10, 10, &[(1.0, 1.0, 8.0, 8.0)]),
+        );
+        assert_similar(
+            &draw(FillRule::EvenOdd),
+            &reference(
+                10,
+                10,
+                &[
+                    (1.0, 1.0, 8.0, 2.0),
+                    (1.0, 7.0, 8.0, 2.0),
+                    (1.0, 3.0, 2.0, 4.0),
+                    (7.0, 3.0, 2.0, 4.0),
+                ],
+            ),
+        );
+    }
+}
@@ -27,6 +27,7 @@ pub fn convert_image_to_base64_url(image: &Image) -> EcoString {
             RasterFormat::Png => "png",
             RasterFormat::Jpg => "jpeg",
             RasterFormat::Gif => "gif",
+            RasterFormat::Webp => "webp",
         },
         ImageFormat::Vector(f) => match f {
             VectorFormat::Svg => "svg+xml",
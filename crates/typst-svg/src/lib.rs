@@ -222,7 +222,7 @@ impl SVGRenderer {
     fn render_group(&mut self, state: State, group: &GroupItem) {
         let state = match group.frame.kind() {
             FrameKind::Soft => state.pre_concat(group.transform),
-            FrameKind::Hard => state
+            FrameKind::Hard | FrameKind::Container => state
                 .with_transform(Transform::identity())
                 .with_size(group.frame.size()),
         };
@@ -443,3 +443,51 @@ impl ttf_parser::OutlineBuilder for SvgPathBuilder {
         write!(&mut self.0, "Z ").unwrap();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use typst::syntax::Span;
+    use typst::visualize::{Color, Geometry, Path};
+
+    use super::*;
+
+    /// A frame with two identical scaled and clipped groups holding a square.
+    fn frame() -> Frame {
+        let size = Size::splat(Abs::pt(5.0));
+        let mut inner = Frame::hard(size);
+        inner.push(
+            Point::zero(),
+            FrameItem::Shape(
+                Geometry::Rect(size).filled(Color::RED.into()),
+                Span::detached(),
+            ),
+        );
+
+        let mut group = GroupItem::new(inner);
+        group.transform = Transform::scale(Ratio::new(2.0), Ratio::new(2.0));
+        group.clip_path = Some(Path::rect(size));
+
+        let mut frame = Frame::hard(Size::splat(Abs::pt(20.0)));
+        frame.push(Point::zero(), FrameItem::Group(group.clone()));
+        frame.push(Point::splat(Abs::pt(10.0)), FrameItem::Group(group));
+        frame
+    }
+
+    #[test]
+    fn test_svg_deterministic() {
+        assert_eq!(svg(&frame()), svg(&frame()));
+    }
+
+    #[test]
+    fn test_svg_groups() {
+        let svg = svg(&frame());
+        assert!(svg.starts_with("<svg class=\"typst-doc\" viewBox=\"0 0 20 20\""));
+        assert_eq!(svg.matches("<g class=\"typst-group\" clip-path=\"url(#c").count(), 2);
+        assert_eq!(svg.matches("<g transform=\"matrix(2 0 0 2 0 0)\">").count(), 2);
+        assert_eq!(svg.matches("<g transform=\"translate(10 10)\">").count(), 1);
+        assert_eq!(svg.matches("fill=\"#ff4136\"").count(), 2);
+
+        // Identical clip paths are only defined once.
+        assert_eq!(svg.matches("<clipPath id=\"c").count(), 1);
+    }
+}
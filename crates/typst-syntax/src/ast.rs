@@ -1870,6 +1870,12 @@ node! {
 }
 
 impl<'a> SetRule<'a> {
+    /// Whether this is a `set default` rule, whose properties yield to those
+    /// of regular set rules regardless of where they appear.
+    pub fn is_default(self) -> bool {
+        self.0.children().any(|child| child.kind() == SyntaxKind::Default)
+    }
+
     /// The function to set style properties for.
     pub fn target(self) -> Expr<'a> {
         self.0.cast_first_match().unwrap_or_default()
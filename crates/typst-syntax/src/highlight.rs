@@ -232,6 +232,7 @@ pub fn highlight(node: &LinkedNode) -> Option<Tag> {
         SyntaxKind::Auto => Some(Tag::Keyword),
         SyntaxKind::Let => Some(Tag::Keyword),
         SyntaxKind::Set => Some(Tag::Keyword),
+        SyntaxKind::Default => Some(Tag::Keyword),
         SyntaxKind::Show => Some(Tag::Keyword),
         SyntaxKind::Context => Some(Tag::Keyword),
         SyntaxKind::If => Some(Tag::Keyword),
@@ -173,6 +173,9 @@ pub enum SyntaxKind {
     Let,
     /// The `set` keyword.
     Set,
+    /// The `default` modifier of a set rule. Only a keyword directly after
+    /// `set`, so it remains a valid identifier elsewhere.
+    Default,
     /// The `show` keyword.
     Show,
     /// The `context` keyword.
@@ -334,6 +337,7 @@ impl SyntaxKind {
                 | Self::Auto
                 | Self::Let
                 | Self::Set
+                | Self::Default
                 | Self::Show
                 | Self::Context
                 | Self::If
@@ -446,6 +450,7 @@ impl SyntaxKind {
             Self::Auto => "`auto`",
             Self::Let => "keyword `let`",
             Self::Set => "keyword `set`",
+            Self::Default => "keyword `default`",
             Self::Show => "keyword `show`",
             Self::Context => "keyword `context`",
             Self::If => "keyword `if`",
@@ -302,11 +302,16 @@ impl Lexer<'_> {
     }
 
     fn blocky_raw(&mut self, start: usize, end: usize, backticks: usize) {
-        // Language tag.
+        // Language tag. It must be followed by whitespace or the closing
+        // backticks, otherwise it is part of the text.
         self.s.jump(start + backticks);
         if self.s.eat_if(is_id_start) {
             self.s.eat_while(is_id_continue);
-            self.push_raw(SyntaxKind::RawLang);
+            if self.s.cursor() == end - backticks || self.s.at(char::is_whitespace) {
+                self.push_raw(SyntaxKind::RawLang);
+            } else {
+                self.s.jump(start + backticks);
+            }
         }
 
         // Determine inner content between backticks.
@@ -26,7 +26,7 @@ pub use self::node::{LinkedChildren, LinkedNode, Side, SyntaxError, SyntaxNode};
 pub use self::parser::{parse, parse_code, parse_math};
 pub use self::path::VirtualPath;
 pub use self::source::Source;
-pub use self::span::{Span, Spanned};
+pub use self::span::{collect_spanned, Span, Spanned};
 
 use self::lexer::{LexMode, Lexer};
 use self::parser::{reparse_block, reparse_markup};
@@ -893,6 +893,19 @@ fn set_rule(p: &mut Parser) {
     let m = p.marker();
     p.assert(SyntaxKind::Set);
 
+    // `default` is only a modifier if the target follows it. Otherwise, it's
+    // the target itself.
+    if p.at(SyntaxKind::Ident) && p.current_text() == "default" && {
+        let mut lexer = p.lexer.clone();
+        let mut next = lexer.next();
+        while next.is_trivia() {
+            next = lexer.next();
+        }
+        next == SyntaxKind::Ident
+    } {
+        p.convert(SyntaxKind::Default);
+    }
+
     let m2 = p.marker();
     p.expect(SyntaxKind::Ident);
     while p.eat_if(SyntaxKind::Dot) {
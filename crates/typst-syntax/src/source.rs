@@ -168,6 +168,93 @@ impl Source {
         Some(self.find(span)?.range())
     }
 
+    /// Get the span of the parent of the node with the given span.
+    ///
+    /// Like [`find`](Self::find), this uses the span numbering to only descend
+    /// into the one subtree that can contain the span. It thus runs in time
+    /// proportional to the node's depth times the number of children along the
+    /// path instead of the size of the whole tree.
+    ///
+    /// Returns `None` if the span is detached, does not point into this source
+    /// file, or belongs to the root node.
+    pub fn parent(&self, span: Span) -> Option<Span> {
+        Some(self.find(span)?.parent()?.span())
+    }
+
+    /// Get the span of the previous non-trivia sibling of the node with the
+    /// given span.
+    ///
+    /// Has the same complexity and detached-span behaviour as
+    /// [`parent`](Self::parent).
+    pub fn prev_sibling(&self, span: Span) -> Option<Span> {
+        Some(self.find(span)?.prev_sibling()?.span())
+    }
+
+    /// Get the span of the next non-trivia sibling of the node with the given
+    /// span.
+    ///
+    /// Has the same complexity and detached-span behaviour as
+    /// [`parent`](Self::parent).
+    pub fn next_sibling(&self, span: Span) -> Option<Span> {
+        Some(self.find(span)?.next_sibling()?.span())
+    }
+
+    /// Get the span of the smallest node that contains the nodes with both
+    /// spans.
+    ///
+    /// A detached span is ignored, so joining it with another span yields the
+    /// other one. Since no node can contain nodes from two files, the result
+    /// is detached if any of the spans points into a different source file
+    /// than this one or if both are detached.
+    pub fn join(&self, a: Span, b: Span) -> Span {
+        let (a, b) = match (a.is_detached(), b.is_detached()) {
+            (true, true) => return Span::detached(),
+            (true, false) => (b, b),
+            (false, true) => (a, a),
+            (false, false) => (a, b),
+        };
+
+        if a.id() != Some(self.id()) || b.id() != Some(self.id()) {
+            return Span::detached();
+        }
+
+        let Some(mut node) = self.find(a) else { return Span::detached() };
+        if self.find(b).is_none() {
+            return Span::detached();
+        }
+
+        // Walk up from `a` until we reach a node that also contains `b`.
+        loop {
+            if node.find(b).is_some() {
+                return node.span();
+            }
+            let Some(parent) = node.parent().cloned() else {
+                return Span::detached();
+            };
+            node = parent;
+        }
+    }
+
+    /// Get the span of the smallest node that contains the nodes with all the
+    /// given spans, by [joining](Self::join) them.
+    ///
+    /// Detached spans are skipped. The result is detached if there are no
+    /// spans at all, if all of them are detached, or if they point into
+    /// different source files. In the last case, there is no single node
+    /// covering all of them, so the result is detached even if some of the
+    /// spans point into this file.
+    pub fn merge_all(&self, spans: impl IntoIterator<Item = Span>) -> Span {
+        let mut merged = None;
+        for span in spans.into_iter().filter(|span| !span.is_detached()) {
+            let joined = self.join(merged.unwrap_or(span), span);
+            if joined.is_detached() {
+                return Span::detached();
+            }
+            merged = Some(joined);
+        }
+        merged.unwrap_or(Span::detached())
+    }
+
     /// Return the index of the UTF-16 code unit at the byte index.
     pub fn byte_to_utf16(&self, byte_idx: usize) -> Option<usize> {
         let line_idx = self.byte_to_line(byte_idx)?;
@@ -191,10 +278,19 @@ impl Source {
     /// The column is defined as the number of characters in the line before the
     /// byte index.
     pub fn byte_to_column(&self, byte_idx: usize) -> Option<usize> {
+        self.byte_to_line_column(byte_idx).map(|(_, column)| column)
+    }
+
+    /// Return the indices of the line and column at the byte index.
+    ///
+    /// This looks up the line only once, so prefer it over separate calls to
+    /// [`byte_to_line`](Self::byte_to_line) and
+    /// [`byte_to_column`](Self::byte_to_column) when both are needed.
+    pub fn byte_to_line_column(&self, byte_idx: usize) -> Option<(usize, usize)> {
         let line = self.byte_to_line(byte_idx)?;
         let start = self.line_to_byte(line)?;
         let head = self.get(start..byte_idx)?;
-        Some(head.chars().count())
+        Some((line, head.chars().count()))
     }
 
     /// Return the byte index at the UTF-16 code unit.
@@ -320,6 +416,7 @@ fn len_utf16(string: &str) -> usize {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::SyntaxKind;
 
     const TEST: &str = "ä\tcde\nf💛g\r\nhi\rjkl";
 
@@ -439,4 +536,49 @@ mod tests {
         // Test removing everything.
         test(TEST, 0..21, "", "");
     }
+
+    #[test]
+    fn test_source_file_span_navigation() {
+        let source = Source::detached("#f(a, b)");
+        let call = source.root().children().nth(1).unwrap();
+        let args = call.children().nth(1).unwrap();
+        let a = args.children().nth(1).unwrap().span();
+        let b = args.children().nth(4).unwrap().span();
+        let comma = source.next_sibling(a).unwrap();
+        assert_eq!(source.find(comma).unwrap().kind(), SyntaxKind::Comma);
+        assert_eq!(source.prev_sibling(b), Some(comma));
+        assert_eq!(source.parent(a), Some(args.span()));
+        assert_eq!(source.parent(args.span()), Some(call.span()));
+        assert_eq!(source.parent(source.root().span()), None);
+        assert_eq!(source.next_sibling(Span::detached()), None);
+    }
+
+    #[test]
+    fn test_source_file_join_spans() {
+        let source = Source::detached("#f(a, b)");
+        let call = source.root().children().nth(1).unwrap();
+        let args = call.children().nth(1).unwrap();
+        let a = args.children().nth(1).unwrap().span();
+        let b = args.children().nth(4).unwrap().span();
+        assert_eq!(source.join(a, b), args.span());
+        assert_eq!(source.join(a, a), a);
+        assert_eq!(source.join(a, Span::detached()), a);
+        assert_eq!(source.join(call.span(), a), call.span());
+    }
+
+    #[test]
+    fn test_source_file_merge_all() {
+        let source = Source::detached("#f(a, b)");
+        let call = source.root().children().nth(1).unwrap();
+        let args = call.children().nth(1).unwrap();
+        let a = args.children().nth(1).unwrap().span();
+        let b = args.children().nth(4).unwrap().span();
+        let id = FileId::new(None, VirtualPath::new("other.typ"));
+        let other = Source::new(id, "#g(c)".into()).root().span();
+        assert!(source.merge_all([]).is_detached());
+        assert!(source.merge_all([Span::detached(), Span::detached()]).is_detached());
+        assert_eq!(source.merge_all([Span::detached(), a, b]), args.span());
+        assert_eq!(source.merge_all([a]), a);
+        assert!(source.merge_all([a, other]).is_detached());
+    }
 }
@@ -4,7 +4,7 @@ use std::ops::Range;
 
 use ecow::EcoString;
 
-use crate::FileId;
+use crate::{FileId, Source};
 
 /// A unique identifier for a syntax node.
 ///
@@ -83,6 +83,23 @@ impl Span {
         self.0.get() & ((1 << Self::BITS) - 1)
     }
 
+    /// Describe where the span points to as `file:line:col` for logging.
+    ///
+    /// The line and column are one-based, like in text editors, and refer to
+    /// the start of the span's range in the given source. Detached spans are
+    /// described as `<detached>`. For a span that does not point into the
+    /// given source, only its file is known.
+    pub fn debug_location(self, source: &Source) -> String {
+        let Some(id) = self.id() else { return "<detached>".into() };
+        match source
+            .range(self)
+            .and_then(|range| source.byte_to_line_column(range.start))
+        {
+            Some((line, column)) => format!("{id:?}:{}:{}", line + 1, column + 1),
+            None => format!("{id:?}"),
+        }
+    }
+
     /// Resolve a file location relative to this span's source.
     pub fn resolve_path(self, path: &str) -> Result<FileId, EcoString> {
         let Some(file) = self.id() else {
@@ -119,6 +136,45 @@ impl<T> Spanned<T> {
     {
         Spanned { v: f(self.v), span: self.span }
     }
+
+    /// Replace a detached span with the given fallback span.
+    ///
+    /// Keeps the span as is if it is not detached.
+    pub fn span_or(self, fallback: Span) -> Self {
+        let span = if self.span.is_detached() { fallback } else { self.span };
+        Spanned { v: self.v, span }
+    }
+
+    /// Attach the span in-place if the current one is detached.
+    ///
+    /// Like [`span_or`](Self::span_or), this never clobbers a real span, so
+    /// calling it repeatedly keeps the first real span that was attached.
+    pub fn fuse(&mut self, span: Span) {
+        if self.span.is_detached() {
+            self.span = span;
+        }
+    }
+}
+
+/// Collect spanned values into a spanned vector.
+///
+/// The span of the vector is the span of the smallest node in the source that
+/// contains all element spans, as determined by [`Source::merge_all`]. If there
+/// are no elements, all of them are detached, or they point into different
+/// files, it is detached.
+pub fn collect_spanned<T>(
+    source: &Source,
+    items: impl IntoIterator<Item = Spanned<T>>,
+) -> Spanned<Vec<T>> {
+    let mut spans = vec![];
+    let v = items
+        .into_iter()
+        .map(|item| {
+            spans.push(item.span);
+            item.v
+        })
+        .collect();
+    Spanned { v, span: source.merge_all(spans) }
 }
 
 impl<T: Debug> Debug for Spanned<T> {
@@ -129,7 +185,7 @@ impl<T: Debug> Debug for Spanned<T> {
 
 #[cfg(test)]
 mod tests {
-    use crate::{FileId, Span};
+    use crate::{collect_spanned, FileId, LinkedNode, Side, Source, Span, Spanned};
 
     #[test]
     fn test_span_encoding() {
@@ -138,4 +194,103 @@ mod tests {
         assert_eq!(span.id(), Some(id));
         assert_eq!(span.number(), 10);
     }
+
+    #[test]
+    fn test_spanned_span_or() {
+        let id = FileId::from_raw(5);
+        let span = Span::new(id, 10).unwrap();
+        let fallback = Span::new(id, 20).unwrap();
+        assert_eq!(Spanned::new(1, span).span_or(fallback).span, span);
+        assert_eq!(Spanned::new(1, Span::detached()).span_or(fallback).span, fallback);
+        assert!(Spanned::new(1, Span::detached())
+            .span_or(Span::detached())
+            .span
+            .is_detached());
+    }
+
+    #[test]
+    fn test_spanned_fuse() {
+        let id = FileId::from_raw(5);
+        let first = Span::new(id, 10).unwrap();
+        let second = Span::new(id, 20).unwrap();
+        let mut spanned = Spanned::new(1, Span::detached());
+        spanned.fuse(first);
+        assert_eq!(spanned.span, first);
+        spanned.fuse(second);
+        assert_eq!(spanned.span, first);
+    }
+
+    #[test]
+    fn test_span_debug_location() {
+        let source = Source::detached("#let a = 1\r\n#let bé = 2\n\n#let c = a");
+        let span_of = |offset| {
+            let root = LinkedNode::new(source.root());
+            root.leaf_at(offset, Side::After).unwrap().span()
+        };
+
+        let path = format!("{:?}", source.id());
+        assert_eq!(span_of(1).debug_location(&source), format!("{path}:1:2"));
+        assert_eq!(span_of(21).debug_location(&source), format!("{path}:2:9"));
+        assert_eq!(span_of(31).debug_location(&source), format!("{path}:4:6"));
+        assert_eq!(Span::detached().debug_location(&source), "<detached>");
+
+        let other = Source::detached("");
+        assert_eq!(span_of(1).debug_location(&other), path);
+    }
+
+    #[test]
+    fn test_collect_spanned() {
+        let source = Source::detached("#f(a, b)");
+        let call = source.root().children().nth(1).unwrap();
+        let args = call.children().nth(1).unwrap();
+        let a = args.children().nth(1).unwrap().span();
+        let b = args.children().nth(4).unwrap().span();
+
+        let empty = collect_spanned(&source, Vec::<Spanned<i32>>::new());
+        assert!(empty.v.is_empty());
+        assert!(empty.span.is_detached());
+
+        // Sibling spans are covered by their parent.
+        let all = collect_spanned(&source, [Spanned::new(1, b), Spanned::new(2, a)]);
+        assert_eq!(all.v, [1, 2]);
+        assert_eq!(all.span, args.span());
+
+        let mixed = collect_spanned(
+            &source,
+            [
+                Spanned::new(1, Span::detached()),
+                Spanned::new(2, b),
+                Spanned::new(3, Span::detached()),
+            ],
+        );
+        assert_eq!(mixed.v, [1, 2, 3]);
+        assert_eq!(mixed.span, b);
+
+        let detached = collect_spanned(
+            &source,
+            [Spanned::new(1, Span::detached()), Spanned::new(2, Span::detached())],
+        );
+        assert_eq!(detached.v, [1, 2]);
+        assert!(detached.span.is_detached());
+    }
+
+    #[test]
+    fn test_span_stable_across_edits() {
+        let span_at = |source: &Source, offset| {
+            let root = LinkedNode::new(source.root());
+            let leaf = root.leaf_at(offset, Side::After).unwrap();
+            assert_eq!(leaf.text(), "c");
+            (leaf.span(), leaf.range())
+        };
+
+        let mut source = Source::detached("#let a = 1\n#let b = 2\n#let c = 3");
+        let (before, range) = span_at(&source, 27);
+
+        // Renumber the nodes of `b`'s definition, which shifts the range of
+        // `c`, but not its span.
+        source.edit(20..21, "22");
+        let (after, shifted) = span_at(&source, 28);
+        assert_eq!(before, after);
+        assert_ne!(range, shifted);
+    }
 }
@@ -0,0 +1,74 @@
+//! Compares batched introspection queries against running each query on its
+//! own.
+//!
+//! Run with `cargo bench -p typst --bench query`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use typst::foundations::{NativeElement, Selector, Value};
+use typst::introspection::{Introspector, Location, MetadataElem, Tag};
+use typst::layout::{Abs, Frame, FrameItem, Page, Point, Size};
+
+/// The number of elements in the document.
+const ELEMS: usize = 10_000;
+
+/// The number of selectors that are queried.
+const SELECTORS: usize = 50;
+
+/// How often each variant is run.
+const RUNS: u32 = 20;
+
+fn main() {
+    let pages = document();
+    let metadata = MetadataElem::elem();
+    let value = metadata.field_id("value").unwrap();
+    let selectors: Vec<_> = (0..SELECTORS as i64)
+        .map(|i| {
+            Selector::Elem(metadata, Some(smallvec::smallvec![(value, Value::Int(i))]))
+        })
+        .collect();
+
+    let repeated = measure(&pages, |introspector| {
+        for selector in &selectors {
+            black_box(introspector.query(selector));
+        }
+    });
+    let batched = measure(&pages, |introspector| {
+        black_box(introspector.query_many(&selectors));
+    });
+
+    println!("{SELECTORS} selectors over {ELEMS} elements, averaged over {RUNS} runs");
+    println!("  repeated query: {repeated:?}");
+    println!("  query_many:     {batched:?}");
+}
+
+/// A single page with metadata elements whose values cycle through the
+/// selectors' values.
+fn document() -> Vec<Page> {
+    let mut frame = Frame::soft(Size::zero());
+    for i in 0..ELEMS {
+        let value = Value::Int((i % SELECTORS) as i64);
+        let loc = Location::new(i as u128);
+        let elem = MetadataElem::new(value).pack().located(loc);
+        frame.push(
+            Point::with_y(Abs::pt(i as f64)),
+            FrameItem::Tag(Tag::new(elem, i as u128)),
+        );
+    }
+    vec![Page { frame, numbering: None, number: 1 }]
+}
+
+/// The average time `f` takes on a freshly built introspector, whose query
+/// cache is still empty.
+fn measure(pages: &[Page], f: impl Fn(&Introspector)) -> Duration {
+    let mut total = Duration::ZERO;
+    for _ in 0..RUNS {
+        let mut introspector = Introspector::default();
+        introspector.rebuild(pages);
+        let start = Instant::now();
+        f(&introspector);
+        total += start.elapsed();
+    }
+    total / RUNS
+}
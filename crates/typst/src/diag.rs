@@ -177,10 +177,11 @@ pub enum Severity {
 
 impl SourceDiagnostic {
     /// Create a new, bare error.
+    #[track_caller]
     pub fn error(span: Span, message: impl Into<EcoString>) -> Self {
         Self {
             severity: Severity::Error,
-            span,
+            span: DetachedSpanGuard::check(span),
             trace: eco_vec![],
             message: message.into(),
             hints: eco_vec![],
@@ -188,10 +189,11 @@ impl SourceDiagnostic {
     }
 
     /// Create a new, bare warning.
+    #[track_caller]
     pub fn warning(span: Span, message: impl Into<EcoString>) -> Self {
         Self {
             severity: Severity::Warning,
-            span,
+            span: DetachedSpanGuard::check(span),
             trace: eco_vec![],
             message: message.into(),
             hints: eco_vec![],
@@ -216,6 +218,31 @@ impl SourceDiagnostic {
     }
 }
 
+/// Catches detached spans escaping into diagnostics.
+///
+/// Diagnostics with detached spans can't point the user to the problem. With
+/// the `detached-span-check` feature enabled, every such diagnostic is logged
+/// together with a backtrace so that the lossy site can be found. Without the
+/// feature, this does nothing.
+struct DetachedSpanGuard;
+
+impl DetachedSpanGuard {
+    /// Check a span that is about to end up in a diagnostic.
+    #[track_caller]
+    #[inline]
+    fn check(span: Span) -> Span {
+        #[cfg(feature = "detached-span-check")]
+        if span.is_detached() {
+            log::warn!(
+                "diagnostic with detached span created at {}\n{}",
+                std::panic::Location::caller(),
+                std::backtrace::Backtrace::force_capture(),
+            );
+        }
+        span
+    }
+}
+
 impl From<SyntaxError> for SourceDiagnostic {
     fn from(error: SyntaxError) -> Self {
         Self {
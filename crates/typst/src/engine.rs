@@ -1,19 +1,31 @@
 //! Definition of the central compilation context.
 
+use std::any::{Any, TypeId};
 use std::collections::HashSet;
+use std::hash::{Hash, Hasher};
+use std::num::NonZeroUsize;
 use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
 
 use comemo::{Track, Tracked, TrackedMut, Validate};
-use ecow::EcoVec;
+use ecow::{eco_format, eco_vec, EcoString, EcoVec};
 use rayon::iter::{IndexedParallelIterator, IntoParallelIterator, ParallelIterator};
 
-use crate::diag::{SourceDiagnostic, SourceResult};
-use crate::foundations::{Styles, Value};
-use crate::introspection::Introspector;
+use crate::diag::{At, SourceDiagnostic, SourceResult};
+use crate::foundations::{Content, Label, Selector, Styles, Value};
+use crate::introspection::{Introspector, Location};
+use crate::layout::Rect;
 use crate::syntax::{FileId, Span};
 use crate::World;
 
 /// Holds all data needed during compilation.
+///
+/// There is intentionally no place for arbitrary mutable state that is shared
+/// between unrelated layout calls. Layout functions are memoized, so a call
+/// that hits the cache doesn't run again and wouldn't update such state. State
+/// that must cross function boundaries instead flows through the
+/// [`Introspector`] (e.g. via counters and `state`), which is recomputed each
+/// pass, or through the [`Sink`], whose updates comemo replays on cache hits.
 pub struct Engine<'a> {
     /// The compilation environment.
     pub world: Tracked<'a, dyn World + 'a>,
@@ -81,10 +93,284 @@ impl Engine<'_> {
         for (_, sink) in &mut pairs {
             let sink = std::mem::take(sink);
             self.sink.extend(sink.delayed, sink.warnings, sink.values);
+            if sink.invalidated {
+                self.sink.invalidate();
+            }
         }
 
         pairs.into_iter().map(|(output, _)| output)
     }
+
+    /// Requests another layout pass even if all introspections stabilize.
+    ///
+    /// This is for features that feed measurements back into layout, like
+    /// balancing content based on its measured size. The request only
+    /// applies to the current pass and the pass limit still applies, so
+    /// requesting a pass can't make compilation loop forever.
+    ///
+    /// The request goes through the [`Sink`] and is thus replayed when a
+    /// memoized call that made it is reused. A call that invalidates must
+    /// therefore depend on something that changes between passes (e.g. an
+    /// introspection), or it requests a new pass each time until the limit
+    /// is reached.
+    pub fn invalidate(&mut self) {
+        self.sink.invalidate();
+    }
+
+    /// Resolves a label to the unique element it is attached to and the
+    /// element's location.
+    ///
+    /// Fails with an error at the given span if the label does not exist in
+    /// the document or if it occurs multiple times.
+    pub fn resolve_label(
+        &self,
+        label: Label,
+        span: Span,
+    ) -> SourceResult<(Location, &Content)> {
+        let elem = self.introspector.query_label(label).at(span)?;
+        Ok((elem.location().unwrap(), elem))
+    }
+
+    /// Iterates over the elements matching a selector alongside their
+    /// locations.
+    ///
+    /// The query is performed and recorded eagerly when this is called, not
+    /// when the iterator is driven. That way, the dependency on the matches is
+    /// always tracked completely, even if the iterator is dropped early, and
+    /// convergence checks stay correct. Since query results are shared with
+    /// the introspector's query cache, the matches are not copied: The
+    /// iterator only hands out clones of the individual elements.
+    pub fn locate_iter(
+        &self,
+        selector: &Selector,
+    ) -> impl Iterator<Item = (Location, Content)> {
+        self.introspector
+            .query(selector)
+            .into_iter()
+            .map(|elem| (elem.location().unwrap(), elem))
+    }
+
+    /// Finds the unique element matching a selector alongside its location.
+    ///
+    /// This is meant for singleton metadata, where silently taking the first
+    /// of several matches would hide authoring mistakes. Returns `Ok(None)` if
+    /// nothing matches and fails with the first two matches if there are more
+    /// than one. Builds on [`locate_iter`](Self::locate_iter), so the query is
+    /// recorded eagerly, but no element beyond the second is cloned.
+    pub fn locate_unique(
+        &self,
+        selector: &Selector,
+    ) -> Result<Option<(Location, Content)>, DuplicateMatch> {
+        let mut iter = self.locate_iter(selector);
+        let Some(first) = iter.next() else { return Ok(None) };
+        match iter.next() {
+            None => Ok(Some(first)),
+            Some(second) => Err(DuplicateMatch {
+                first: (first.0, first.1.span()),
+                second: (second.0, second.1.span()),
+            }),
+        }
+    }
+
+    /// Finds the elements matching each of the selectors alongside their
+    /// locations.
+    ///
+    /// This is more efficient than calling [`locate_iter`](Self::locate_iter)
+    /// for each selector because simple selectors are matched in a single pass
+    /// over the document. See [`Introspector::query_many`] for details.
+    pub fn locate_many(&self, selectors: &[Selector]) -> Vec<Vec<(Location, Content)>> {
+        self.introspector
+            .query_many(selectors)
+            .into_iter()
+            .map(|elems| {
+                elems
+                    .into_iter()
+                    .map(|elem| (elem.location().unwrap(), elem))
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Finds the location of the first element matching a selector in document
+    /// order.
+    ///
+    /// Query results are kept in document order, so this neither sorts the
+    /// matches nor clones any element. The dependency is recorded on the
+    /// returned location only: Changes to the matches that leave the first
+    /// one in place don't invalidate the caller.
+    pub fn first_location(&self, selector: &Selector) -> Option<Location> {
+        self.introspector.first_location(selector)
+    }
+
+    /// Finds the location of the last element matching a selector in document
+    /// order.
+    ///
+    /// See [`first_location`](Self::first_location) for details.
+    pub fn last_location(&self, selector: &Selector) -> Option<Location> {
+        self.introspector.last_location(selector)
+    }
+
+    /// Iterates over the elements matching a selector that are laid out at the
+    /// top level of their page, alongside their locations.
+    ///
+    /// Elements nested in boxes, blocks, or transformed content are skipped.
+    /// See [`Introspector::depth`] for the precise definition. Like with
+    /// [`locate_iter`](Self::locate_iter), the query is recorded eagerly.
+    pub fn locate_top_level(
+        &self,
+        selector: &Selector,
+    ) -> impl Iterator<Item = (Location, Content)> {
+        self.introspector
+            .roots(selector)
+            .into_iter()
+            .map(|elem| (elem.location().unwrap(), elem))
+    }
+
+    /// Iterates over the elements matching a selector that lie within a
+    /// rectangle on the given page, alongside their locations.
+    ///
+    /// See [`Introspector::query_in_rect`] for how membership is decided. Like
+    /// with [`locate_iter`](Self::locate_iter), the query is recorded eagerly.
+    pub fn locate_in_rect(
+        &self,
+        page: NonZeroUsize,
+        rect: Rect,
+        selector: &Selector,
+    ) -> impl Iterator<Item = (Location, Content)> {
+        self.introspector
+            .query_in_rect(selector, page, rect)
+            .into_iter()
+            .map(|elem| (elem.location().unwrap(), elem))
+    }
+
+    /// Queries for the elements matching a selector alongside a digest of the
+    /// matches.
+    ///
+    /// The digest is a hash of the matches, including their locations. It
+    /// stays the same across introspection passes as long as the matches do,
+    /// so consumers can cheaply detect whether a query's result changed.
+    pub fn query_cached(&self, selector: &Selector) -> (EcoVec<Content>, u128) {
+        let elems = self.introspector.query(selector);
+        let digest = crate::utils::hash128(&elems);
+        (elems, digest)
+    }
+
+    /// Computes a value derived from the elements matching a selector, reusing
+    /// the value from an earlier introspection pass if the matches did not
+    /// change.
+    ///
+    /// The query goes through the tracked introspector, so the caller depends
+    /// on the matches just like with a plain query. The derivation itself is
+    /// memoized with comemo, keyed by the matches and the `id`, so its results
+    /// are evicted like those of any other memoized call. Hence, `f` must be a
+    /// pure function of its input and each deriving function needs an `id` of
+    /// its own, e.g. the name of the caller. The function itself can't serve
+    /// as the key since its address is not guaranteed to be unique.
+    pub fn memoize_query<T>(
+        &self,
+        id: &'static str,
+        selector: &Selector,
+        f: fn(&[Content]) -> T,
+    ) -> T
+    where
+        T: Clone + Send + Sync + 'static,
+    {
+        let elems = self.introspector.query(selector);
+        derive(&elems, Derivation::new(id, &f))
+            .downcast_ref::<T>()
+            .expect("derived value has the type of the deriving function")
+            .clone()
+    }
+}
+
+/// Derives a value from query matches.
+#[comemo::memoize]
+fn derive(elems: &EcoVec<Content>, derivation: Derivation) -> Arc<dyn Any + Send + Sync> {
+    (derivation.call)(derivation.f, elems)
+}
+
+/// A deriving function for [`Engine::memoize_query`] with its output type
+/// erased, so that it can be passed to a memoized function.
+///
+/// Hashes by the id of the function and its output type.
+#[derive(Copy, Clone)]
+struct Derivation<'a> {
+    /// The deriving function, a `fn(&[Content]) -> T`.
+    f: &'a (dyn Any + Send + Sync),
+    /// Calls `f`, knowing its concrete type.
+    call: fn(&(dyn Any + Send + Sync), &[Content]) -> Arc<dyn Any + Send + Sync>,
+    /// The id of `f` and the type of its output.
+    key: (&'static str, TypeId),
+}
+
+impl<'a> Derivation<'a> {
+    /// Erases the output type of a deriving function.
+    fn new<T>(id: &'static str, f: &'a fn(&[Content]) -> T) -> Self
+    where
+        T: Send + Sync + 'static,
+    {
+        Self {
+            f,
+            call: |f, elems| {
+                let f = f.downcast_ref::<fn(&[Content]) -> T>().unwrap();
+                Arc::new(f(elems))
+            },
+            key: (id, TypeId::of::<T>()),
+        }
+    }
+}
+
+impl Hash for Derivation<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key.hash(state);
+    }
+}
+
+/// The error of [`Engine::locate_unique`] if a selector matches multiple
+/// elements.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct DuplicateMatch {
+    /// The location and span of the first match in document order.
+    pub first: (Location, Span),
+    /// The location and span of the second match in document order.
+    pub second: (Location, Span),
+}
+
+impl DuplicateMatch {
+    /// Turns the duplicate into an error.
+    ///
+    /// The second match is the one that breaks uniqueness, so the error
+    /// points to it and hints at where the first one is, unless we don't know
+    /// where the second one comes from.
+    pub fn report(self, world: Tracked<dyn World + '_>) -> EcoVec<SourceDiagnostic> {
+        let (_, first) = self.first;
+        let (_, second) = self.second;
+        let span = if second.is_detached() { first } else { second };
+        let mut diag =
+            SourceDiagnostic::error(span, "selector matches multiple elements")
+                .with_hint("only one matching element is allowed");
+        if span != first {
+            if let Some(position) = describe_position(world, first) {
+                diag.hint(eco_format!("the first matching element is at {position}"));
+            }
+        }
+        eco_vec![diag]
+    }
+}
+
+/// Describes where a span points to as `path:line:column`, if it is attached
+/// to a source file.
+fn describe_position(world: Tracked<dyn World + '_>, span: Span) -> Option<EcoString> {
+    let id = span.id()?;
+    let source = world.source(id).ok()?;
+    let start = source.range(span)?.start;
+    let (line, column) = source.byte_to_line_column(start)?;
+    Some(eco_format!(
+        "{}:{}:{}",
+        id.vpath().as_rootless_path().display(),
+        line + 1,
+        column + 1,
+    ))
 }
 
 /// May hold a span that is currently under inspection.
@@ -116,7 +402,8 @@ impl Traced {
     }
 }
 
-/// A push-only sink for delayed errors, warnings, and traced values.
+/// A push-only sink for delayed errors, warnings, traced values, and requests
+/// for another layout pass.
 ///
 /// All tracked methods of this type are of the form `(&mut self, ..) -> ()`, so
 /// in principle they do not need validation (though that optimization is not
@@ -135,6 +422,9 @@ pub struct Sink {
     warnings_set: HashSet<u128>,
     /// A sequence of traced values for a span.
     values: EcoVec<(Value, Option<Styles>)>,
+    /// Whether another layout pass was requested through
+    /// [`Engine::invalidate`].
+    invalidated: bool,
 }
 
 impl Sink {
@@ -151,6 +441,11 @@ impl Sink {
         std::mem::take(&mut self.delayed)
     }
 
+    /// Whether another layout pass was requested. Resets the request.
+    pub fn invalidated(&mut self) -> bool {
+        std::mem::take(&mut self.invalidated)
+    }
+
     /// Get the stored warnings.
     pub fn warnings(self) -> EcoVec<SourceDiagnostic> {
         self.warnings
@@ -185,6 +480,11 @@ impl Sink {
         }
     }
 
+    /// Request another layout pass.
+    pub fn invalidate(&mut self) {
+        self.invalidated = true;
+    }
+
     /// Extend from another sink.
     fn extend(
         &mut self,
@@ -347,3 +647,148 @@ impl Clone for Route<'_> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::foundations::{func, NativeElement};
+    use crate::introspection::testing::{tags_page, with_engine, TestWorld};
+    use crate::introspection::MetadataElem;
+    use crate::Library;
+
+    /// Requests another layout pass if `when` is true.
+    #[func]
+    fn invalidate(
+        /// The engine.
+        engine: &mut Engine,
+        /// Whether to request another pass.
+        when: bool,
+    ) {
+        if when {
+            engine.invalidate();
+        }
+    }
+
+    #[test]
+    fn test_sink_invalidate() {
+        let mut sink = Sink::new();
+        assert!(!sink.invalidated());
+
+        // A request is consumed by the pass that checks for it, so a single
+        // request yields exactly one extra pass.
+        sink.track_mut().invalidate();
+        assert!(sink.invalidated());
+        assert!(!sink.invalidated());
+    }
+
+    #[test]
+    fn test_compile_invalidate() {
+        let converges = |text: &str| {
+            let mut library = Library::builder().with_max_iterations(3).build();
+            library.global.scope_mut().define_func::<invalidate>();
+            let world = TestWorld::with_library(text, library);
+            let warned = crate::compile(&world);
+            assert!(warned.output.is_ok());
+            !warned
+                .warnings
+                .iter()
+                .any(|warning| warning.message.starts_with("layout did not converge"))
+        };
+
+        // Without a request, a stable layout is final.
+        assert!(converges("#context invalidate(false)"));
+
+        // The same layout is redone as long as a pass requests it, until the
+        // pass limit stops it.
+        assert!(!converges("#context invalidate(true)"));
+
+        // A request that depends on an introspection is not renewed once the
+        // introspection changes, so the layout finishes in the next pass.
+        assert!(converges(
+            "#context invalidate(query(<done>).len() == 0)\n#metadata(none) <done>"
+        ));
+    }
+
+    #[test]
+    fn test_engine_memoize_query() {
+        static CALLS: AtomicUsize = AtomicUsize::new(0);
+        fn count(elems: &[Content]) -> usize {
+            CALLS.fetch_add(1, Ordering::SeqCst);
+            elems.len()
+        }
+
+        // The ids are unique to this test, so no other test shares the
+        // memoized values.
+        let selector = MetadataElem::elem().select();
+        let len = |id, values: &[i64]| {
+            let mut introspector = Introspector::default();
+            introspector.rebuild(&[tags_page(values)]);
+            with_engine(&introspector, |engine| {
+                engine.memoize_query(id, &selector, count)
+            })
+        };
+
+        // A later pass with the same matches reuses the derived value.
+        assert_eq!(len("test-memoize-query", &[1, 2]), 2);
+        assert_eq!(len("test-memoize-query", &[1, 2]), 2);
+        assert_eq!(CALLS.load(Ordering::SeqCst), 1);
+
+        // Changed matches are derived anew.
+        assert_eq!(len("test-memoize-query", &[1, 2, 3]), 3);
+        assert_eq!(CALLS.load(Ordering::SeqCst), 2);
+
+        // So is the same function under another id.
+        assert_eq!(len("test-memoize-query-other", &[1, 2]), 2);
+        assert_eq!(CALLS.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn test_engine_locate_unique() {
+        let selector = MetadataElem::elem().select();
+        let locate = |values: &[i64]| {
+            let mut introspector = Introspector::default();
+            introspector.rebuild(&[tags_page(values)]);
+            with_engine(&introspector, |engine| engine.locate_unique(&selector))
+        };
+
+        assert_eq!(locate(&[]), Ok(None));
+        let (location, elem) = locate(&[1]).unwrap().unwrap();
+        assert_eq!(location, Location::new(1));
+        assert_eq!(elem.location(), Some(location));
+
+        let duplicate = locate(&[1, 2, 3]).unwrap_err();
+        assert_eq!(duplicate.first.0, Location::new(1));
+        assert_eq!(duplicate.second.0, Location::new(2));
+
+        // The error points at the second match and hints at the first one.
+        let world = TestWorld::new("#f(a, b)");
+        let source = world.main();
+        let call = source.root().children().nth(1).unwrap();
+        let args = call.children().nth(1).unwrap();
+        let a = args.children().nth(1).unwrap().span();
+        let b = args.children().nth(4).unwrap().span();
+        let report = |first, second| {
+            DuplicateMatch {
+                first: (duplicate.first.0, first),
+                second: (duplicate.second.0, second),
+            }
+            .report((&world as &dyn World).track())
+        };
+
+        let diags = report(a, b);
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].span, b);
+        assert_eq!(
+            diags[0].hints.as_slice(),
+            [
+                "only one matching element is allowed",
+                "the first matching element is at main.typ:1:4",
+            ]
+        );
+
+        // Without a span for the second match, the error points at the first.
+        let diags = report(a, Span::detached());
+        assert_eq!(diags[0].span, a);
+        assert_eq!(diags[0].hints.as_slice(), ["only one matching element is allowed"]);
+    }
+}
@@ -4,7 +4,7 @@ use std::cmp::Ordering;
 
 use ecow::eco_format;
 
-use crate::diag::{bail, At, HintedStrResult, SourceResult, StrResult};
+use crate::diag::{bail, error, At, HintedStrResult, SourceResult, StrResult};
 use crate::eval::{access_dict, Access, Eval, Vm};
 use crate::foundations::{format_str, Datetime, IntoValue, Regex, Repr, Value};
 use crate::layout::{Alignment, Length, Rel};
@@ -405,8 +405,13 @@ fn is_zero(v: &Value) -> bool {
 }
 
 /// Try to divide two lengths.
-fn try_div_length(a: Length, b: Length) -> StrResult<f64> {
-    a.try_div(b).ok_or_else(|| "cannot divide these two lengths".into())
+fn try_div_length(a: Length, b: Length) -> HintedStrResult<f64> {
+    a.try_div(b).ok_or_else(|| {
+        error!(
+            "cannot divide these two lengths";
+            hint: "use `.to-absolute()` within a context to resolve their em units first"
+        )
+    })
 }
 
 /// Try to divide two relative lengths.
@@ -24,7 +24,8 @@ impl Eval for ast::SetRule<'_> {
             })
             .at(target.span())?;
         let args = self.args().eval(vm)?.spanned(self.span());
-        Ok(target.set(&mut vm.engine, args)?.spanned(self.span()))
+        let styles = target.set(&mut vm.engine, args)?.spanned(self.span());
+        Ok(if self.is_default() { styles.defaults() } else { styles })
     }
 }
 
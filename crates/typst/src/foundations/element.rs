@@ -152,7 +152,14 @@ impl Repr for Element {
 
 impl Ord for Element {
     fn cmp(&self, other: &Self) -> Ordering {
-        self.name().cmp(other.name())
+        // Names are not unique (e.g. `item` for lists and enums), so distinct
+        // elements with the same name are ordered by their static data to stay
+        // consistent with equality.
+        self.name().cmp(other.name()).then_with(|| {
+            let a: *const NativeElementData = self.0 .0;
+            let b: *const NativeElementData = other.0 .0;
+            a.cmp(&b)
+        })
     }
 }
 
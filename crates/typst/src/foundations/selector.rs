@@ -1,18 +1,24 @@
 use std::any::{Any, TypeId};
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
 use std::sync::Arc;
 
 use comemo::Tracked;
 use ecow::{eco_format, EcoString, EcoVec};
+use once_cell::sync::Lazy;
+use serde::{de, ser, Deserialize, Deserializer, Serialize, Serializer};
 use smallvec::SmallVec;
 
 use crate::diag::{bail, HintedStrResult, StrResult};
 use crate::foundations::{
     cast, func, repr, scope, ty, CastInfo, Content, Context, Dict, Element, FromValue,
-    Func, Label, Reflect, Regex, Repr, Str, StyleChain, Type, Value,
+    Func, Label, Reflect, Regex, Repr, Scope, Str, StyleChain, Type, Value,
 };
 use crate::introspection::{Introspector, Locatable, Location};
+use crate::layout::{Ratio, Transform};
 use crate::symbols::Symbol;
 use crate::text::TextElem;
+use crate::Library;
 
 /// A helper macro to create a field selector used in [`Selector::Elem`]
 #[macro_export]
@@ -77,8 +83,15 @@ pub enum Selector {
     /// Matches a specific type of element.
     ///
     /// If there is a dictionary, only elements with the fields from the
-    /// dictionary match.
+    /// dictionary match. The element is checked first, so fields are only
+    /// compared for elements of the right kind.
     Elem(Element, Option<SmallVec<[(u8, Value); 1]>>),
+    /// Matches elements of any of the given types.
+    ///
+    /// This is equivalent to an [`Or`](Self::Or) of plain
+    /// [`Elem`](Self::Elem) selectors, but checks the type with a single
+    /// lookup.
+    ElemIn(ElemSet),
     /// Matches the element at the specified location.
     Location(Location),
     /// Matches elements with a specific label.
@@ -95,6 +108,19 @@ pub enum Selector {
     Before { selector: Arc<Self>, end: Arc<Self>, inclusive: bool },
     /// Matches all matches of `selector` after `start`.
     After { selector: Arc<Self>, start: Arc<Self>, inclusive: bool },
+    /// Matches all matches of `selector` that were laid out with a
+    /// transformation fulfilling the `predicate`.
+    Transform { selector: Arc<Self>, predicate: TransformPredicate },
+    /// Matches all matches of `descendant` that lie within the bounds of a
+    /// match of `ancestor`.
+    ///
+    /// The containment test is spatial: A descendant is contained if its
+    /// position lies on the same page as and within the
+    /// [bounds](crate::introspection::Introspector::bounds) of the ancestor.
+    /// Ancestors without bounds, i.e. those that aren't explicit blocks,
+    /// contain nothing. For a block that breaks across pages, only its first
+    /// part counts.
+    DescendantOf { ancestor: Arc<Self>, descendant: Arc<Self> },
 }
 
 impl Selector {
@@ -117,6 +143,11 @@ impl Selector {
         Ok(Self::Regex(regex))
     }
 
+    /// Define a selector for elements of any of the given types.
+    pub fn elem_in(elems: impl IntoIterator<Item = Element>) -> Self {
+        Self::ElemIn(elems.into_iter().collect())
+    }
+
     /// Define a simple [`Selector::Can`] selector.
     pub fn can<T: ?Sized + Any>() -> Self {
         Self::Can(TypeId::of::<T>())
@@ -131,6 +162,7 @@ impl Selector {
                         target.get(*id, styles).as_ref().ok() == Some(value)
                     })
             }
+            Self::ElemIn(set) => set.contains(target.func()),
             Self::Label(label) => target.label() == Some(*label),
             Self::Regex(regex) => target
                 .to_packed::<TextElem>()
@@ -144,7 +176,10 @@ impl Selector {
             }
             Self::Location(location) => target.location() == Some(*location),
             // Not supported here.
-            Self::Before { .. } | Self::After { .. } => false,
+            Self::Before { .. }
+            | Self::After { .. }
+            | Self::Transform { .. }
+            | Self::DescendantOf { .. } => false,
         }
     }
 }
@@ -226,6 +261,65 @@ impl Selector {
             inclusive,
         }
     }
+
+    /// Returns a modified selector that will only match elements that lie
+    /// within a match of `ancestor`, like the equations in a particular
+    /// theorem box.
+    ///
+    /// Containment is determined spatially: An element lies within an ancestor
+    /// if its position is inside the area of the ancestor's block on the same
+    /// page. Ancestors that aren't laid out as a block of their own contain
+    /// nothing. For a block that breaks across pages, only its first part
+    /// counts.
+    #[func]
+    pub fn descendant_of(
+        self,
+        /// The selector for the containing elements.
+        ancestor: LocatableSelector,
+    ) -> Selector {
+        Self::DescendantOf {
+            ancestor: Arc::new(ancestor.0),
+            descendant: Arc::new(self),
+        }
+    }
+
+    /// Returns a modified selector that will only match elements that were
+    /// laid out with a certain transformation, e.g. within a
+    /// [`rotate`]($rotate) or [`scale`]($scale) call.
+    ///
+    /// Exactly one of the conditions must be given.
+    ///
+    /// ```example
+    /// #rotate(90deg, reflow: true)[#metadata("A")]
+    /// #metadata("B")
+    ///
+    /// #context query(
+    ///   metadata.transformed(rotated: true)
+    /// ).map(it => it.value)
+    /// ```
+    #[func]
+    pub fn transformed(
+        self,
+        /// Whether to match elements that were rotated or skewed. Only
+        /// `{true}` is allowed.
+        #[named]
+        #[default(false)]
+        rotated: bool,
+        /// Match elements that were scaled below this factor along at least
+        /// one axis.
+        #[named]
+        scaled_below: Option<Ratio>,
+    ) -> StrResult<Selector> {
+        let predicate = match (rotated, scaled_below) {
+            (true, None) => TransformPredicate::Rotated,
+            (false, Some(factor)) => TransformPredicate::ScaledBelow(factor),
+            (true, Some(_)) => {
+                bail!("cannot combine `rotated` and `scaled-below`")
+            }
+            (false, None) => bail!("expected `rotated: true` or `scaled-below`"),
+        };
+        Ok(Self::Transform { selector: Arc::new(self), predicate })
+    }
 }
 
 impl From<Location> for Selector {
@@ -249,6 +343,17 @@ impl Repr for Selector {
                     elem.name().into()
                 }
             }
+            Self::ElemIn(set) => {
+                // Names are ambiguous, so we use paths where possible.
+                let mut pieces: Vec<_> = set
+                    .iter()
+                    .map(|elem| {
+                        ELEMENTS.path(elem).unwrap_or_else(|_| elem.name().into())
+                    })
+                    .collect();
+                pieces.sort();
+                eco_format!("or{}", repr::pretty_array_like(&pieces, false))
+            }
             Self::Label(label) => label.repr(),
             Self::Regex(regex) => regex.repr(),
             Self::Can(cap) => eco_format!("{cap:?}"),
@@ -271,6 +376,12 @@ impl Repr for Selector {
                     inclusive_arg
                 )
             }
+            Self::Transform { selector, predicate } => {
+                eco_format!("{}.transformed({})", selector.repr(), predicate.repr())
+            }
+            Self::DescendantOf { ancestor, descendant } => {
+                eco_format!("{}.descendant-of({})", descendant.repr(), ancestor.repr())
+            }
         }
     }
 }
@@ -287,6 +398,342 @@ cast! {
     location: Location => Self::Location(location),
 }
 
+impl Serialize for Selector {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        SelectorRepr::try_from(self)
+            .map_err(ser::Error::custom)?
+            .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Selector {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        SelectorRepr::deserialize(deserializer)?
+            .try_into()
+            .map_err(de::Error::custom)
+    }
+}
+
+/// The serialized form of a selector.
+///
+/// Elements are identified by their path in the standard library (e.g.
+/// `footnote.entry`) and locations by their hash in hexadecimal. Capability
+/// selectors refer to Rust types and thus cannot be serialized.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+enum SelectorRepr {
+    Elem { element: EcoString, fields: Option<Vec<(EcoString, Value)>> },
+    ElemIn(Vec<EcoString>),
+    Location(EcoString),
+    Label(EcoString),
+    Regex(EcoString),
+    Or(Vec<Selector>),
+    And(Vec<Selector>),
+    Before { selector: Box<Selector>, end: Box<Selector>, inclusive: bool },
+    After { selector: Box<Selector>, start: Box<Selector>, inclusive: bool },
+    Transform { selector: Box<Selector>, predicate: TransformPredicate },
+    DescendantOf { ancestor: Box<Selector>, descendant: Box<Selector> },
+}
+
+impl TryFrom<&Selector> for SelectorRepr {
+    type Error = EcoString;
+
+    fn try_from(selector: &Selector) -> StrResult<Self> {
+        Ok(match selector {
+            Selector::Elem(elem, fields) => Self::Elem {
+                element: ELEMENTS.path(*elem)?,
+                fields: fields
+                    .as_ref()
+                    .map(|fields| {
+                        fields
+                            .iter()
+                            .map(|(id, value)| {
+                                let name = elem.field_name(*id).unwrap();
+                                if !round_trips(value) {
+                                    bail!(
+                                        "field `{name}` of `{}` holds a {}, which \
+                                         cannot be serialized",
+                                        elem.name(),
+                                        value.ty(),
+                                    );
+                                }
+                                Ok((name.into(), value.clone()))
+                            })
+                            .collect::<StrResult<_>>()
+                    })
+                    .transpose()?,
+            },
+            Selector::ElemIn(set) => Self::ElemIn(
+                set.iter().map(|elem| ELEMENTS.path(elem)).collect::<StrResult<_>>()?,
+            ),
+            Selector::Location(loc) => Self::Location(eco_format!("{:032x}", loc.hash())),
+            Selector::Label(label) => Self::Label(label.as_str().into()),
+            Selector::Regex(regex) => Self::Regex(regex.as_str().into()),
+            Selector::Can(_) => bail!("capability selectors cannot be serialized"),
+            Selector::Or(list) => Self::Or(list.to_vec()),
+            Selector::And(list) => Self::And(list.to_vec()),
+            Selector::Before { selector, end, inclusive } => Self::Before {
+                selector: Box::new(selector.as_ref().clone()),
+                end: Box::new(end.as_ref().clone()),
+                inclusive: *inclusive,
+            },
+            Selector::After { selector, start, inclusive } => Self::After {
+                selector: Box::new(selector.as_ref().clone()),
+                start: Box::new(start.as_ref().clone()),
+                inclusive: *inclusive,
+            },
+            Selector::Transform { selector, predicate } => Self::Transform {
+                selector: Box::new(selector.as_ref().clone()),
+                predicate: *predicate,
+            },
+            Selector::DescendantOf { ancestor, descendant } => Self::DescendantOf {
+                ancestor: Box::new(ancestor.as_ref().clone()),
+                descendant: Box::new(descendant.as_ref().clone()),
+            },
+        })
+    }
+}
+
+impl TryFrom<SelectorRepr> for Selector {
+    type Error = EcoString;
+
+    fn try_from(repr: SelectorRepr) -> StrResult<Self> {
+        Ok(match repr {
+            SelectorRepr::Elem { element, fields } => {
+                let elem = ELEMENTS.element(&element)?;
+                let fields = fields
+                    .map(|fields| {
+                        fields
+                            .into_iter()
+                            .map(|(name, value)| match elem.field_id(&name) {
+                                Some(id) => Ok((id, value)),
+                                None => Err(eco_format!(
+                                    "element `{element}` has no field `{name}`"
+                                )),
+                            })
+                            .collect::<StrResult<_>>()
+                    })
+                    .transpose()?;
+                Self::Elem(elem, fields)
+            }
+            SelectorRepr::ElemIn(paths) => Self::ElemIn(
+                paths
+                    .iter()
+                    .map(|path| ELEMENTS.element(path))
+                    .collect::<StrResult<_>>()?,
+            ),
+            SelectorRepr::Location(hex) => Self::Location(Location::new(
+                u128::from_str_radix(&hex, 16)
+                    .map_err(|_| eco_format!("invalid location `{hex}`"))?,
+            )),
+            SelectorRepr::Label(label) => {
+                if label.is_empty() {
+                    bail!("label name must not be empty");
+                }
+                Self::Label(Label::new(label.as_str()))
+            }
+            SelectorRepr::Regex(regex) => Self::regex(Regex::new(&regex)?)?,
+            SelectorRepr::Or(list) => Self::Or(list.into_iter().collect()),
+            SelectorRepr::And(list) => Self::And(list.into_iter().collect()),
+            SelectorRepr::Before { selector, end, inclusive } => Self::Before {
+                selector: Arc::new(*selector),
+                end: Arc::new(*end),
+                inclusive,
+            },
+            SelectorRepr::After { selector, start, inclusive } => Self::After {
+                selector: Arc::new(*selector),
+                start: Arc::new(*start),
+                inclusive,
+            },
+            SelectorRepr::Transform { selector, predicate } => {
+                Self::Transform { selector: Arc::new(*selector), predicate }
+            }
+            SelectorRepr::DescendantOf { ancestor, descendant } => Self::DescendantOf {
+                ancestor: Arc::new(*ancestor),
+                descendant: Arc::new(*descendant),
+            },
+        })
+    }
+}
+
+/// Whether a field value deserializes to an equal value.
+///
+/// Values of other types are serialized as their representation and would thus
+/// come back as strings, which never match the original field.
+fn round_trips(value: &Value) -> bool {
+    match value {
+        Value::None | Value::Bool(_) | Value::Int(_) | Value::Str(_) => true,
+        Value::Float(v) => v.is_finite(),
+        Value::Array(array) => array.iter().all(round_trips),
+        Value::Dict(dict) => dict.iter().all(|(_, value)| round_trips(value)),
+        _ => false,
+    }
+}
+
+/// The element functions of the standard library, by path and vice versa.
+static ELEMENTS: Lazy<ElementPaths> = Lazy::new(|| {
+    let mut paths = ElementPaths::default();
+    paths.collect(Library::default().global.scope(), "");
+    paths
+});
+
+/// Maps between element functions and their paths in the standard library.
+#[derive(Default)]
+struct ElementPaths {
+    /// The first path at which each element was found.
+    paths: HashMap<Element, EcoString>,
+    /// All paths at which elements are reachable.
+    elements: HashMap<EcoString, Element>,
+}
+
+impl ElementPaths {
+    /// The path of an element function.
+    fn path(&self, elem: Element) -> StrResult<EcoString> {
+        self.paths
+            .get(&elem)
+            .cloned()
+            .ok_or_else(|| eco_format!("element `{}` cannot be serialized", elem.name()))
+    }
+
+    /// The element function at a path.
+    fn element(&self, path: &str) -> StrResult<Element> {
+        match self.elements.get(path) {
+            Some(&elem) => Ok(elem),
+            None => bail!("unknown element `{path}`"),
+        }
+    }
+
+    /// Recursively collect the element functions defined in a scope.
+    fn collect(&mut self, scope: &Scope, prefix: &str) {
+        for (name, value) in scope.iter() {
+            let path = eco_format!("{prefix}{name}");
+            let scope = match value {
+                Value::Func(func) => {
+                    if let Some(elem) = func.element() {
+                        self.paths.entry(elem).or_insert_with(|| path.clone());
+                        self.elements.insert(path.clone(), elem);
+                    }
+                    func.scope()
+                }
+                Value::Module(module) => Some(module.scope()),
+                _ => None,
+            };
+            if let Some(scope) = scope {
+                self.collect(scope, &eco_format!("{path}."));
+            }
+        }
+    }
+}
+
+/// A set of element types, as matched by [`Selector::ElemIn`].
+///
+/// Hashing is independent of the order in which the elements were given, so
+/// that equal sets share their entries in the introspector's query cache.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ElemSet(Arc<HashSet<Element>>);
+
+impl ElemSet {
+    /// Whether the set contains the element.
+    pub fn contains(&self, elem: Element) -> bool {
+        self.0.contains(&elem)
+    }
+
+    /// The elements in the set, ordered by name. Elements with the same name
+    /// are ordered in a fixed, but otherwise unspecified way.
+    pub fn iter(&self) -> impl Iterator<Item = Element> {
+        let mut elems: Vec<_> = self.0.iter().copied().collect();
+        elems.sort();
+        elems.into_iter()
+    }
+}
+
+impl FromIterator<Element> for ElemSet {
+    fn from_iter<T: IntoIterator<Item = Element>>(iter: T) -> Self {
+        Self(Arc::new(iter.into_iter().collect()))
+    }
+}
+
+impl Hash for ElemSet {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.len().hash(state);
+        for elem in self.iter() {
+            elem.hash(state);
+        }
+    }
+}
+
+/// A condition on the accumulated transformation of an element.
+///
+/// The transformation is the one of the element's position on its page, so it
+/// combines all transforms of the groups the element is nested in. Only the
+/// linear part is considered, translations never matter.
+#[derive(Debug, Copy, Clone, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TransformPredicate {
+    /// The element's axes are not aligned with the page's axes anymore, i.e.
+    /// it was rotated or skewed.
+    Rotated,
+    /// The element was scaled below the given factor along at least one
+    /// axis.
+    ScaledBelow(#[serde(with = "ratio")] Ratio),
+}
+
+/// Serializes ratios as plain floats.
+mod ratio {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::layout::Ratio;
+
+    pub fn serialize<S: Serializer>(
+        ratio: &Ratio,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        ratio.get().serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Ratio, D::Error> {
+        f64::deserialize(deserializer).map(Ratio::new)
+    }
+}
+
+impl TransformPredicate {
+    /// Whether the transformation fulfills the predicate.
+    pub fn matches(self, ts: Transform) -> bool {
+        // Rotations by multiples of 90° yield tiny non-zero sines and
+        // cosines, so we compare approximately.
+        const EPS: f64 = 1e-9;
+        match self {
+            Self::Rotated => {
+                ts.kx.get().abs() > EPS
+                    || ts.ky.get().abs() > EPS
+                    || (ts.sx.get() < -EPS && ts.sy.get() < -EPS)
+            }
+            Self::ScaledBelow(factor) => {
+                let x = ts.sx.get().hypot(ts.ky.get());
+                let y = ts.kx.get().hypot(ts.sy.get());
+                x.min(y) < factor.get() - EPS
+            }
+        }
+    }
+}
+
+impl Repr for TransformPredicate {
+    fn repr(&self) -> EcoString {
+        match self {
+            Self::Rotated => "rotated: true".into(),
+            Self::ScaledBelow(factor) => eco_format!("scaled-below: {}", factor.repr()),
+        }
+    }
+}
+
 /// A selector that can be used with `query`.
 ///
 /// Hopefully, this is made obsolete by a more powerful query mechanism in the
@@ -347,6 +794,13 @@ impl FromValue for LocatableSelector {
                         Err(eco_format!("{} is not locatable", elem.name()))?
                     }
                 }
+                Selector::ElemIn(set) => {
+                    for elem in set.iter() {
+                        if !elem.can::<dyn Locatable>() {
+                            Err(eco_format!("{} is not locatable", elem.name()))?
+                        }
+                    }
+                }
                 Selector::Location(_) => {}
                 Selector::Label(_) => {}
                 Selector::Regex(_) => bail!("text is not locatable"),
@@ -362,6 +816,12 @@ impl FromValue for LocatableSelector {
                         validate(selector)?;
                     }
                 }
+                Selector::Transform { selector, .. } => validate(selector)?,
+                Selector::DescendantOf { ancestor, descendant } => {
+                    for selector in [ancestor, descendant] {
+                        validate(selector)?;
+                    }
+                }
             }
             Ok(())
         }
@@ -425,6 +885,7 @@ impl FromValue for ShowableSelector {
         fn validate(selector: &Selector, nested: bool) -> HintedStrResult<()> {
             match selector {
                 Selector::Elem(_, _) => {}
+                Selector::ElemIn(_) => {}
                 Selector::Label(_) => {}
                 Selector::Regex(_) if !nested => {}
                 Selector::Or(list) | Selector::And(list) => {
@@ -436,7 +897,9 @@ impl FromValue for ShowableSelector {
                 | Selector::Location(_)
                 | Selector::Can(_)
                 | Selector::Before { .. }
-                | Selector::After { .. } => {
+                | Selector::After { .. }
+                | Selector::Transform { .. }
+                | Selector::DescendantOf { .. } => {
                     bail!("this selector cannot be used with show")
                 }
             }
@@ -452,3 +915,112 @@ impl FromValue for ShowableSelector {
         Ok(Self(selector))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use ecow::eco_vec;
+
+    use super::*;
+    use crate::foundations::{select_where, NativeElement};
+    use crate::layout::Ratio;
+    use crate::model::{EnumItem, FootnoteEntry, HeadingElem, ListItem};
+    use crate::text::Lang;
+    use crate::utils::hash128;
+    use crate::visualize::{Color, Paint};
+
+    #[test]
+    fn test_selector_serde_round_trip() {
+        let heading = select_where!(HeadingElem, Depth => NonZeroUsize::new(2).unwrap());
+        let nested = Selector::Transform {
+            selector: Arc::new(Selector::Before {
+                selector: Arc::new(Selector::Or(eco_vec![
+                    heading,
+                    Selector::And(eco_vec![
+                        Selector::Label(Label::new("intro")),
+                        Selector::Elem(FootnoteEntry::elem(), None),
+                    ]),
+                    Selector::text("hello").unwrap(),
+                ])),
+                end: Arc::new(Selector::After {
+                    selector: Arc::new(Selector::Location(Location::new(u128::MAX - 7))),
+                    start: Arc::new(Selector::Label(Label::new("start"))),
+                    inclusive: false,
+                }),
+                inclusive: true,
+            }),
+            predicate: TransformPredicate::ScaledBelow(Ratio::new(0.5)),
+        };
+
+        let json = serde_json::to_string(&nested).unwrap();
+        assert!(json.contains("\"footnote.entry\""));
+        let parsed: Selector = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, nested);
+    }
+
+    #[test]
+    fn test_selector_transform_repr() {
+        let transformed = |predicate| Selector::Transform {
+            selector: Arc::new(HeadingElem::elem().select()),
+            predicate,
+        };
+        assert_eq!(
+            transformed(TransformPredicate::Rotated).repr(),
+            "heading.transformed(rotated: true)"
+        );
+        assert_eq!(
+            transformed(TransformPredicate::ScaledBelow(Ratio::new(0.5))).repr(),
+            "heading.transformed(scaled-below: 50%)"
+        );
+    }
+
+    #[test]
+    fn test_selector_serde_lossy_field() {
+        let red = select_where!(TextElem, Fill => Paint::Solid(Color::RED));
+        let err = serde_json::to_string(&red).unwrap_err();
+        assert!(err.to_string().contains(
+            "field `fill` of `text` holds a color, which cannot be serialized"
+        ));
+
+        let nested = Selector::Or(eco_vec![HeadingElem::elem().select(), red]);
+        assert!(serde_json::to_string(&nested).is_err());
+
+        let english = select_where!(TextElem, Lang => Lang::ENGLISH);
+        let json = serde_json::to_string(&english).unwrap();
+        assert_eq!(serde_json::from_str::<Selector>(&json).unwrap(), english);
+    }
+
+    #[test]
+    fn test_selector_elem_in() {
+        let selector = Selector::elem_in([HeadingElem::elem(), FootnoteEntry::elem()]);
+        assert!(selector.matches(&HeadingElem::new(Content::empty()).pack(), None));
+        assert_eq!(selector.repr(), "or(footnote.entry, heading)");
+
+        let json = serde_json::to_string(&selector).unwrap();
+        assert!(json.contains("\"footnote.entry\""));
+        let parsed: Selector = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, selector);
+    }
+
+    #[test]
+    fn test_selector_elem_in_same_name() {
+        let (list, enum_) = (ListItem::elem(), EnumItem::elem());
+        assert_eq!(list.name(), enum_.name());
+        assert_ne!(list.cmp(&enum_), std::cmp::Ordering::Equal);
+
+        let a = Selector::elem_in([list, enum_, HeadingElem::elem()]);
+        let b = Selector::elem_in([HeadingElem::elem(), enum_, list]);
+        assert_eq!(hash128(&a), hash128(&b));
+        assert_eq!(a.repr(), "or(enum.item, heading, list.item)");
+        assert_eq!(a.repr(), b.repr());
+    }
+
+    #[test]
+    fn test_selector_serde_errors() {
+        let can = Selector::can::<dyn Locatable>();
+        assert!(serde_json::to_string(&can).is_err());
+        let unknown = r#"{"elem":{"element":"nonexistent","fields":null}}"#;
+        assert!(serde_json::from_str::<Selector>(unknown).is_err());
+    }
+}
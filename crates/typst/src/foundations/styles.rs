@@ -126,6 +126,21 @@ impl Styles {
         self
     }
 
+    /// Turn all contained properties into defaults.
+    ///
+    /// Default properties yield to all regular properties in a style chain,
+    /// no matter whether those are further in or out. This is what
+    /// `set default` rules produce and lets templates provide styles that a
+    /// user's set rules override regardless of application order.
+    pub fn defaults(mut self) -> Self {
+        for entry in self.0.make_mut() {
+            if let Style::Property(property) = &mut **entry {
+                property.default = true;
+            }
+        }
+        self
+    }
+
     /// Returns `Some(_)` with an optional span if this list contains
     /// styles for the given element.
     pub fn interruption<T: NativeElement>(&self) -> Option<Option<Span>> {
@@ -235,6 +250,9 @@ pub struct Property {
     value: Block,
     /// The span of the set rule the property stems from.
     span: Option<Span>,
+    /// Whether the property stems from a `set default` rule and thus has lower
+    /// priority than all regular properties in the chain.
+    default: bool,
 }
 
 impl Property {
@@ -249,6 +267,7 @@ impl Property {
             id,
             value: Block::new(value),
             span: None,
+            default: false,
         }
     }
 
@@ -533,16 +552,37 @@ impl<'a> StyleChain<'a> {
     }
 
     /// Iterate over all values for the given property in the chain.
+    ///
+    /// Regular properties are yielded first, followed by default properties
+    /// (from `set default` rules). Within each tier, inner values come before
+    /// outer ones. Both tiers are produced in a single walk over the chain.
     fn properties<T: 'static>(
         self,
         func: Element,
         id: u8,
         inherent: Option<&'a T>,
     ) -> impl Iterator<Item = &'a T> {
+        let mut entries = self.entries();
+        let mut defaults = SmallVec::<[&'a Property; 1]>::new();
+        let mut cursor = 0;
+        let properties = std::iter::from_fn(move || {
+            for entry in entries.by_ref() {
+                let Some(property) = entry.property() else { continue };
+                if !property.is(func, id) {
+                    continue;
+                }
+                if !property.default {
+                    return Some(property);
+                }
+                defaults.push(property);
+            }
+            let property = defaults.get(cursor).copied();
+            cursor += 1;
+            property
+        });
+
         inherent.into_iter().chain(
-            self.entries()
-                .filter_map(Style::property)
-                .filter(move |property| property.is(func, id))
+            properties
                 .map(|property| &property.value)
                 .map(move |value| {
                     value.downcast().unwrap_or_else(|| {
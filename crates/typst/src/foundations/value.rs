@@ -156,9 +156,11 @@ impl Value {
     }
 
     /// Try to access a field on the value.
-    pub fn field(&self, field: &str) -> StrResult<Value> {
+    pub fn field(&self, field: &str) -> HintedStrResult<Value> {
         match self {
-            Self::Symbol(symbol) => symbol.clone().modified(field).map(Self::Symbol),
+            Self::Symbol(symbol) => {
+                return symbol.clone().modified(field).map(Self::Symbol)
+            }
             Self::Version(version) => version.component(field).map(Self::Int),
             Self::Dict(dict) => dict.get(field).cloned(),
             Self::Content(content) => content.field_by_name(field),
@@ -167,6 +169,7 @@ impl Value {
             Self::Module(module) => module.field(field).cloned(),
             _ => fields::field(self, field),
         }
+        .map_err(Into::into)
     }
 
     /// The associated scope, if this is a function, type, or module.
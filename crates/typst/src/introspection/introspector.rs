@@ -1,18 +1,21 @@
-use std::collections::{BTreeSet, HashMap};
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
 use std::fmt::{self, Debug, Formatter};
 use std::hash::Hash;
 use std::num::NonZeroUsize;
-use std::sync::RwLock;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
 
 use ecow::{eco_format, EcoVec};
-use indexmap::IndexMap;
+use indexmap::{IndexMap, IndexSet};
 use smallvec::SmallVec;
 
-use crate::diag::{bail, StrResult};
+use crate::diag::{bail, HintedStrResult, HintedString, StrResult};
 use crate::foundations::{Content, Label, Repr, Selector};
-use crate::introspection::Location;
-use crate::layout::{Frame, FrameItem, Page, Point, Position, Transform};
-use crate::model::Numbering;
+use crate::introspection::{Anchor, Location};
+use crate::layout::{
+    Abs, BlockElem, Frame, FrameItem, Page, Point, Position, Rect, Transform,
+};
+use crate::model::{Destination, Numbering};
 use crate::utils::NonZeroExt;
 
 /// Can be queried for elements and their positions.
@@ -21,7 +24,21 @@ pub struct Introspector {
     /// The number of pages in the document.
     pages: usize,
     /// All introspectable elements.
+    ///
+    /// The elements are cloned from the frames' tags. Since `Content` is
+    /// reference-counted, this shares their storage instead of copying it.
+    /// Interning identical elements would not save anything on top of that:
+    /// Each element carries its unique location, so no two are equal.
     elems: IndexMap<Location, (Content, Position)>,
+    /// The accumulated transforms of elements that were laid out with a
+    /// transform other than a translation.
+    transforms: HashMap<Location, Transform>,
+    /// The depths of elements that are nested in at least one boundary
+    /// group. See [`depth`](Self::depth) for what counts as a boundary.
+    depths: HashMap<Location, usize>,
+    /// The bounds of elements whose tags lead a hard frame, alongside their
+    /// pages. See [`bounds`](Self::bounds) for details.
+    bounds: HashMap<Location, (NonZeroUsize, Rect)>,
     /// Maps labels to their indices in the element list. We use a smallvec such
     /// that if the label is unique, we don't need to allocate.
     labels: HashMap<Label, SmallVec<[usize; 1]>>,
@@ -30,6 +47,19 @@ pub struct Introspector {
     keys: HashMap<u128, SmallVec<[Location; 1]>>,
     /// The page numberings, indexed by page number minus 1.
     page_numberings: Vec<Option<Numbering>>,
+    /// All link destinations alongside the positions of their areas.
+    links: IndexSet<(Destination, Position)>,
+    /// Incremented whenever a rebuild changes the introspectable elements.
+    generation: u64,
+    /// A hash of the introspectable elements, used to detect changes across
+    /// rebuilds.
+    fingerprint: u128,
+    /// Snapshots of the most recent generations, oldest first, including the
+    /// current one. Used to determine what changed since an earlier
+    /// generation. The snapshots have no history of their own.
+    history: VecDeque<(u64, Arc<Introspector>)>,
+    /// How many generations are retained in the history. Zero by default.
+    history_limit: usize,
     /// Caches queries done on the introspector. This is important because
     /// even if all top-level queries are distinct, they often have shared
     /// subqueries. Example: Individual counter queries with `before` that
@@ -38,14 +68,27 @@ pub struct Introspector {
 }
 
 impl Introspector {
+    /// The maximum number of query results that an introspector caches.
+    ///
+    /// Exceeding it never changes results: Once the cache is full, the least
+    /// recently used half of it is evicted and those queries are recomputed
+    /// when they are used again. Documents that perform many more distinct
+    /// queries per layout pass (e.g. a `before` query for each of thousands
+    /// of elements) thus trade some recomputation for bounded memory.
+    pub const QUERY_CACHE_CAPACITY: usize = 4096;
+
     /// Applies new frames in-place, reusing the existing allocations.
     #[typst_macros::time(name = "introspect")]
     pub fn rebuild(&mut self, pages: &[Page]) {
         self.pages = pages.len();
         self.elems.clear();
+        self.transforms.clear();
+        self.depths.clear();
+        self.bounds.clear();
         self.labels.clear();
         self.keys.clear();
         self.page_numberings.clear();
+        self.links.clear();
         self.queries.clear();
 
         for (i, page) in pages.iter().enumerate() {
@@ -53,39 +96,231 @@ impl Introspector {
             self.extract(&page.frame, page_nr, Transform::identity());
             self.page_numberings.push(page.numbering.clone());
         }
+
+        let fingerprint = crate::utils::hash128(self.elems.as_slice());
+        if fingerprint != self.fingerprint {
+            self.fingerprint = fingerprint;
+            self.generation += 1;
+
+            // Remember the new generation. The query cache was cleared above,
+            // so the snapshot only holds the elements and their metadata.
+            if self.history_limit > 0 {
+                let mut history = std::mem::take(&mut self.history);
+                if history.len() >= self.history_limit {
+                    history.pop_front();
+                }
+                history.push_back((self.generation, Arc::new(self.clone())));
+                self.history = history;
+            }
+        }
+    }
+
+    /// Retains the given number of most recent generations for
+    /// [`changed_since`](Self::changed_since).
+    ///
+    /// Each retained generation holds a copy of the introspectable elements,
+    /// so no history is kept by default. Enable it on an introspector that is
+    /// rebuilt repeatedly, e.g. by an editor that keeps its own introspector
+    /// and rebuilds it from the pages of each compiled document. Generations
+    /// that were rebuilt before the history was enabled are not retained. Passing zero disables
+    /// the history again and drops all retained generations.
+    pub fn retain_history(&mut self, generations: usize) {
+        self.history_limit = generations;
+        while self.history.len() > generations {
+            self.history.pop_front();
+        }
+    }
+
+    /// The generation of the introspectable elements.
+    ///
+    /// Starts at zero and is incremented by every rebuild that changes the
+    /// elements, their locations, or their positions. Tools can compare
+    /// generations to detect that nothing introspectable changed between two
+    /// rebuilds of the same introspector. Generations of different
+    /// introspectors, e.g. from separate compilations, are not comparable.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Captures the introspector's current state, including its query cache.
+    ///
+    /// Together with [`restore`](Self::restore), this allows speculative
+    /// layout: Rebuild the introspector from a trial layout, probe it with
+    /// queries, and roll back if the trial is discarded, so that neither its
+    /// elements nor its cached queries leak into the layout iteration that
+    /// drives convergence.
+    ///
+    /// A snapshot is only valid within the `typeset` call it was taken in.
+    /// Locations are not stable across compilations, so restoring a snapshot
+    /// into a different compilation yields meaningless introspection results.
+    pub fn snapshot(&self) -> IntrospectorSnapshot {
+        IntrospectorSnapshot(self.clone())
+    }
+
+    /// Rolls back to a state captured with [`snapshot`](Self::snapshot).
+    ///
+    /// Afterwards, all queries produce exactly the same results as when the
+    /// snapshot was taken, and the generation is reset to the one of the
+    /// snapshot.
+    pub fn restore(&mut self, snapshot: IntrospectorSnapshot) {
+        *self = snapshot.0;
+    }
+
+    /// The locations of the elements, grouped by the key they were located
+    /// with.
+    pub(crate) fn key_locations(&self) -> HashMap<u128, SmallVec<[Location; 1]>> {
+        self.keys.clone()
+    }
+
+    /// Finds a key whose elements were assigned different locations than in a
+    /// previous pass, as returned by [`key_locations`](Self::key_locations).
+    ///
+    /// Only keys are compared, not the elements themselves. If the keys or the
+    /// number of elements per key differ, the document is still changing and
+    /// different locations are expected, so no key is reported. Otherwise, a
+    /// mismatch means that locations were assigned nondeterministically, which
+    /// commonly prevents convergence. The locations of a key are compared
+    /// regardless of their order, since elements with the same key may be
+    /// laid out in a different order without being located differently. If
+    /// there are multiple mismatching keys, the smallest one is returned.
+    pub(crate) fn unstable_key(
+        &self,
+        previous: &HashMap<u128, SmallVec<[Location; 1]>>,
+    ) -> Option<u128> {
+        if self.keys.len() != previous.len()
+            || self.keys.iter().any(|(key, locs)| {
+                previous.get(key).map_or(true, |prev| prev.len() != locs.len())
+            })
+        {
+            return None;
+        }
+
+        let sorted = |locs: &SmallVec<[Location; 1]>| {
+            let mut hashes: SmallVec<[u128; 1]> =
+                locs.iter().map(|loc| loc.hash()).collect();
+            hashes.sort_unstable();
+            hashes
+        };
+
+        self.keys
+            .iter()
+            .filter(|(key, locs)| sorted(&previous[*key]) != sorted(locs))
+            .map(|(&key, _)| key)
+            .min()
+    }
+
+    /// The first element, in document order, that was located with the given
+    /// key.
+    pub(crate) fn first_keyed(&self, key: u128) -> Option<&Content> {
+        self.keys
+            .get(&key)?
+            .iter()
+            .filter_map(|loc| self.get(loc))
+            .min_by_key(|elem| self.elem_index(elem))
+    }
+
+    /// The elements whose label is also attached to other elements, in
+    /// document order.
+    pub(crate) fn duplicately_labelled(&self) -> impl Iterator<Item = &Content> + '_ {
+        let mut indices: Vec<usize> = self
+            .labels
+            .values()
+            .filter(|indices| indices.len() > 1)
+            .flatten()
+            .copied()
+            .collect();
+        indices.sort_unstable();
+        indices.into_iter().map(|index| &self.elems[index].0)
+    }
+
+    /// Limits the number of distinct queries, including nested subqueries,
+    /// that are performed on this introspector between two rebuilds.
+    ///
+    /// Once the budget is exhausted, further queries that aren't cached yet
+    /// are not performed anymore and yield no elements, so that a runaway
+    /// document stops doing expensive work right away. A query that was
+    /// evicted from the cache and is performed again doesn't count again.
+    /// Whether this happened can be checked with
+    /// [`query_budget_exceeded`](Self::query_budget_exceeded). By default,
+    /// the number of queries is unlimited.
+    pub(crate) fn set_query_budget(&mut self, budget: usize) {
+        self.queries.budget = budget;
+    }
+
+    /// Whether queries were refused since the last rebuild because the
+    /// [query budget](Self::set_query_budget) was exhausted.
+    pub(crate) fn query_budget_exceeded(&self) -> bool {
+        self.queries.exhausted()
+    }
+
+    /// The queries that were performed on this introspector and are still
+    /// cached, alongside their results.
+    pub(crate) fn cached_queries(&self) -> Vec<(Selector, EcoVec<Content>)> {
+        self.queries.queries()
+    }
+
+    /// Finds a query whose results differ from those in a previous pass, as
+    /// returned by [`cached_queries`](Self::cached_queries).
+    ///
+    /// This is used to point out what keeps the layout from converging. If
+    /// multiple queries changed, the one with the shortest representation is
+    /// returned, since it is most likely the root cause rather than derived
+    /// from it (e.g. a `before` query on top of a changed query). Ties are
+    /// broken by the representation itself, so that the result is
+    /// deterministic.
+    pub(crate) fn unstable_query(
+        &self,
+        previous: &[(Selector, EcoVec<Content>)],
+    ) -> Option<Selector> {
+        previous
+            .iter()
+            .filter(|(selector, output)| self.query(selector) != *output)
+            .map(|(selector, _)| (selector.repr(), selector))
+            .min_by(|(a, _), (b, _)| a.len().cmp(&b.len()).then_with(|| a.cmp(b)))
+            .map(|(_, selector)| selector.clone())
     }
 
     /// Extract metadata from a frame.
     fn extract(&mut self, frame: &Frame, page: NonZeroUsize, ts: Transform) {
-        for (pos, item) in frame.items() {
-            match item {
-                FrameItem::Group(group) => {
-                    let ts = ts
-                        .pre_concat(Transform::translate(pos.x, pos.y))
-                        .pre_concat(group.transform);
-                    self.extract(&group.frame, page, ts);
-                }
-                FrameItem::Tag(tag)
-                    if !self.elems.contains_key(&tag.elem.location().unwrap()) =>
-                {
-                    let pos = pos.transform(ts);
-                    let loc = tag.elem.location().unwrap();
-                    let ret = self
-                        .elems
-                        .insert(loc, (tag.elem.clone(), Position { page, point: pos }));
-                    assert!(ret.is_none(), "duplicate locations");
-
-                    // Build the key map.
-                    self.keys.entry(tag.key).or_default().push(loc);
-
-                    // Build the label cache.
-                    if let Some(label) = tag.elem.label() {
-                        self.labels.entry(label).or_default().push(self.elems.len() - 1);
-                    }
-                }
-                _ => {}
+        let mut extraction = Extraction::default();
+        extraction.walk(frame, page, ts, 0);
+
+        for meta in extraction.metas {
+            // An element may have been recorded on an earlier page already.
+            let loc = meta.location;
+            if self.elems.contains_key(&loc) {
+                continue;
+            }
+
+            let label = meta.elem.label();
+            let ret = self.elems.insert(loc, (meta.elem, meta.position));
+            debug_assert!(ret.is_none(), "duplicate locations");
+
+            if !meta.transform.is_identity() {
+                self.transforms.insert(loc, meta.transform);
+            }
+
+            if meta.depth > 0 {
+                self.depths.insert(loc, meta.depth);
+            }
+
+            // Build the key map.
+            self.keys.entry(meta.key).or_default().push(loc);
+
+            // Build the label cache.
+            if let Some(label) = label {
+                self.labels.entry(label).or_default().push(self.elems.len() - 1);
             }
         }
+
+        for (loc, rect) in extraction.bounds {
+            self.bounds.entry(loc).or_insert((page, rect));
+        }
+
+        // Nested frames with the same destination are each post-processed,
+        // so the same link can occur multiple times at one position. The set
+        // makes sure we record it once.
+        self.links.extend(extraction.links);
     }
 
     /// Iterate over all locatable elements.
@@ -93,6 +328,48 @@ impl Introspector {
         self.elems.values().map(|(c, _)| c)
     }
 
+    /// Serializes all locatable elements to JSON for external tools.
+    ///
+    /// Each element becomes an object with its location (as a hexadecimal
+    /// string, like in [`Location::encode`]), its position and the element
+    /// itself, serialized like with `typst query`. The elements are sorted by
+    /// location, so that the output is deterministic. For example:
+    ///
+    /// ```json
+    /// [{
+    ///   "location": "0f3a...",
+    ///   "page": 1,
+    ///   "x": 10.0,
+    ///   "y": 42.5,
+    ///   "element": { "func": "heading", "level": 1, "body": ... }
+    /// }]
+    /// ```
+    ///
+    /// The positions are given in points. For just the elements matching a
+    /// selector, use the CLI's `typst query <file> <selector> --format json`
+    /// instead.
+    ///
+    /// Fails if an element holds a value that can't be serialized.
+    pub fn to_json(&self) -> StrResult<serde_json::Value> {
+        let mut elems: Vec<_> = self.elems.iter().collect();
+        elems.sort_by_key(|(loc, _)| loc.hash());
+        elems
+            .into_iter()
+            .map(|(loc, (elem, pos))| {
+                let element = serde_json::to_value(elem).map_err(|err| {
+                    eco_format!("failed to serialize {}: {err}", elem.func().name())
+                })?;
+                Ok(serde_json::json!({
+                    "location": format!("{:032x}", loc.hash()),
+                    "page": pos.page.get(),
+                    "x": pos.point.x.to_pt(),
+                    "y": pos.point.y.to_pt(),
+                    "element": element,
+                }))
+            })
+            .collect()
+    }
+
     /// Perform a binary search for `elem` among the `list`.
     fn binary_search(&self, list: &[Content], elem: &Content) -> Result<usize, usize> {
         list.binary_search_by_key(&self.elem_index(elem), |elem| self.elem_index(elem))
@@ -114,15 +391,142 @@ impl Introspector {
     }
 }
 
+/// Extracts the locatable elements from a frame, in the same way the
+/// introspector does for the pages of a document.
+///
+/// Nested groups are descended into and their transforms applied, so the
+/// returned positions are relative to the frame passed in, transformed by
+/// `ts`. Each element is reported once, at its first occurrence.
+pub fn extract_meta(
+    frame: &Frame,
+    page: NonZeroUsize,
+    ts: Transform,
+) -> Vec<ExtractedMeta> {
+    let mut extraction = Extraction::default();
+    extraction.walk(frame, page, ts, 0);
+    extraction.metas
+}
+
+/// Everything the introspector needs from a frame, gathered in a single walk
+/// over its items and those of its nested groups.
+#[derive(Default)]
+struct Extraction {
+    /// The locatable elements, each at its first occurrence.
+    metas: Vec<ExtractedMeta>,
+    /// The locations of the elements in `metas`.
+    seen: HashSet<Location>,
+    /// The bounds of blocks, in the order their frames occur.
+    bounds: Vec<(Location, Rect)>,
+    /// Link destinations alongside the positions of their areas.
+    links: Vec<(Destination, Position)>,
+}
+
+impl Extraction {
+    /// Walk over the items of a frame that is nested in `depth` boundary
+    /// groups.
+    fn walk(&mut self, frame: &Frame, page: NonZeroUsize, ts: Transform, depth: usize) {
+        for &(pos, ref item) in frame.items() {
+            match item {
+                FrameItem::Group(group) => {
+                    let ts = ts
+                        .pre_concat(Transform::translate(pos.x, pos.y))
+                        .pre_concat(group.transform);
+                    if group.frame.kind().is_container() {
+                        self.record_bounds(&group.frame, ts);
+                    }
+                    let depth = depth + usize::from(group.is_boundary());
+                    self.walk(&group.frame, page, ts, depth);
+                }
+                FrameItem::Tag(tag) => {
+                    let location = tag.elem.location().unwrap();
+                    if self.seen.insert(location) {
+                        self.metas.push(ExtractedMeta {
+                            location,
+                            elem: tag.elem.clone(),
+                            key: tag.key,
+                            position: Position { page, point: pos.transform(ts) },
+                            transform: Transform {
+                                tx: Abs::zero(),
+                                ty: Abs::zero(),
+                                ..ts
+                            },
+                            depth,
+                        });
+                    }
+                }
+                FrameItem::Link(dest, _) => {
+                    let point = pos.transform(ts);
+                    self.links.push((dest.clone(), Position { page, point }));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Record the bounds of a block from the container frame it leads.
+    ///
+    /// When a block is laid out, the pending tags of the flow are prepended to
+    /// its first frame. The last of them is the block's own start tag, while
+    /// the others belong to preceding elements that have no frame of their
+    /// own. The transformed bounding box of the frame is thus the block's
+    /// extent on the page. A block that breaks across pages only gets the
+    /// bounds of its first frame, since the others aren't tagged.
+    fn record_bounds(&mut self, frame: &Frame, ts: Transform) {
+        let own = frame
+            .items()
+            .map_while(|(_, item)| match item {
+                FrameItem::Tag(tag) => Some(tag),
+                _ => None,
+            })
+            .last()
+            .filter(|tag| tag.elem.is::<BlockElem>());
+        if let Some(tag) = own {
+            let rect = Rect::from_pos_size(Point::zero(), frame.size()).transform(ts);
+            self.bounds.push((tag.elem.location().unwrap(), rect));
+        }
+    }
+}
+
+/// A locatable element extracted from a frame by [`extract_meta`].
+#[derive(Debug, Clone)]
+pub struct ExtractedMeta {
+    /// The element's location.
+    pub location: Location,
+    /// The element itself.
+    pub elem: Content,
+    /// The key the element was located with.
+    pub key: u128,
+    /// The position of the element's tag.
+    pub position: Position,
+    /// The transform of the element's tag, without its translation.
+    pub transform: Transform,
+    /// The number of boundary groups the element's tag is nested in. See
+    /// [`FlatItem::depth`](crate::layout::FlatItem::depth).
+    pub depth: usize,
+}
+
 #[comemo::track]
 impl Introspector {
     /// Query for all matching elements.
+    ///
+    /// The elements are returned in document order, i.e. in the order in
+    /// which their tags occur in the pages' frames. This also decides between
+    /// elements at the same position, so the order is reproducible without
+    /// any further sort key. Each location occurs at most once, since an
+    /// element that is laid out multiple times is only recorded at its first
+    /// occurrence.
     pub fn query(&self, selector: &Selector) -> EcoVec<Content> {
         let hash = crate::utils::hash128(selector);
         if let Some(output) = self.queries.get(hash) {
             return output;
         }
 
+        // Once the query budget is exhausted, the compilation is going to be
+        // aborted, so there is no point in doing the work.
+        if !self.queries.admit(hash) {
+            return EcoVec::new();
+        }
+
         let output = match selector {
             Selector::Label(label) => self
                 .labels
@@ -131,7 +535,7 @@ impl Introspector {
                     indices.iter().map(|&index| self.elems[index].0.clone()).collect()
                 })
                 .unwrap_or_default(),
-            Selector::Elem(..) | Selector::Can(_) => self
+            Selector::Elem(..) | Selector::ElemIn(_) | Selector::Can(_) => self
                 .all()
                 .filter(|elem| selector.matches(elem, None))
                 .cloned()
@@ -167,6 +571,36 @@ impl Introspector {
                 }
                 list
             }
+            Selector::Transform { selector, predicate } => self
+                .query(selector)
+                .into_iter()
+                .filter(|elem| {
+                    elem.location()
+                        .is_some_and(|loc| predicate.matches(self.transform(loc)))
+                })
+                .collect(),
+            Selector::DescendantOf { ancestor, descendant } => {
+                let ancestors: Vec<_> = self
+                    .query(ancestor)
+                    .iter()
+                    .filter_map(|elem| {
+                        let loc = elem.location().unwrap();
+                        self.bounds.get(&loc).map(|&(page, rect)| (loc, page, rect))
+                    })
+                    .collect();
+                self.query(descendant)
+                    .into_iter()
+                    .filter(|elem| {
+                        let loc = elem.location().unwrap();
+                        let pos = self.position(loc);
+                        ancestors.iter().any(|&(ancestor, page, rect)| {
+                            ancestor != loc
+                                && page == pos.page
+                                && rect.contains(pos.point)
+                        })
+                    })
+                    .collect()
+            }
             Selector::And(selectors) => {
                 let mut results: Vec<_> =
                     selectors.iter().map(|sel| self.query(sel)).collect();
@@ -202,10 +636,50 @@ impl Introspector {
             Selector::Regex(_) => EcoVec::new(),
         };
 
-        self.queries.insert(hash, output.clone());
+        self.queries.insert(hash, selector, output.clone());
         output
     }
 
+    /// Query for the elements matching each of the selectors.
+    ///
+    /// The result is the same as calling [`query`](Self::query) for each
+    /// selector, but all element and capability selectors that aren't cached
+    /// yet are matched in a single pass over the elements instead of one pass
+    /// per selector. Each selector's result still gets its own entry in the
+    /// query cache.
+    pub fn query_many(&self, selectors: &[Selector]) -> Vec<EcoVec<Content>> {
+        let mut unique = HashSet::new();
+        let pending: Vec<(u128, &Selector)> = selectors
+            .iter()
+            .filter(|selector| {
+                matches!(
+                    selector,
+                    Selector::Elem(..) | Selector::ElemIn(_) | Selector::Can(_)
+                )
+            })
+            .map(|selector| (crate::utils::hash128(selector), selector))
+            .filter(|&(hash, _)| unique.insert(hash))
+            .filter(|&(hash, _)| self.queries.get(hash).is_none())
+            .filter(|&(hash, _)| self.queries.admit(hash))
+            .collect();
+
+        if !pending.is_empty() {
+            let mut outputs = vec![EcoVec::new(); pending.len()];
+            for elem in self.all() {
+                for (output, (_, selector)) in outputs.iter_mut().zip(&pending) {
+                    if selector.matches(elem, None) {
+                        output.push(elem.clone());
+                    }
+                }
+            }
+            for (output, (hash, selector)) in outputs.into_iter().zip(&pending) {
+                self.queries.insert(*hash, selector, output);
+            }
+        }
+
+        selectors.iter().map(|selector| self.query(selector)).collect()
+    }
+
     /// Query for the first element that matches the selector.
     pub fn query_first(&self, selector: &Selector) -> Option<Content> {
         match selector {
@@ -219,8 +693,26 @@ impl Introspector {
         }
     }
 
+    /// The location of the first element in document order that matches the
+    /// selector.
+    ///
+    /// Unlike [`query_first`](Self::query_first), this doesn't hand out the
+    /// element, so a tracked caller only depends on the location and is not
+    /// invalidated when the element's content changes.
+    pub fn first_location(&self, selector: &Selector) -> Option<Location> {
+        self.query(selector).first().map(|elem| elem.location().unwrap())
+    }
+
+    /// The location of the last element in document order that matches the
+    /// selector.
+    ///
+    /// See [`first_location`](Self::first_location) for details.
+    pub fn last_location(&self, selector: &Selector) -> Option<Location> {
+        self.query(selector).last().map(|elem| elem.location().unwrap())
+    }
+
     /// Query for the first element that matches the selector.
-    pub fn query_unique(&self, selector: &Selector) -> StrResult<Content> {
+    pub fn query_unique(&self, selector: &Selector) -> HintedStrResult<Content> {
         match selector {
             Selector::Location(location) => self
                 .get(location)
@@ -241,13 +733,28 @@ impl Introspector {
     }
 
     /// Query for a unique element with the label.
-    pub fn query_label(&self, label: Label) -> StrResult<&Content> {
+    ///
+    /// If the label occurs multiple times, the error lists all elements that
+    /// carry it alongside their pages.
+    pub fn query_label(&self, label: Label) -> HintedStrResult<&Content> {
         let indices = self.labels.get(&label).ok_or_else(|| {
             eco_format!("label `{}` does not exist in the document", label.repr())
         })?;
 
         if indices.len() > 1 {
-            bail!("label `{}` occurs multiple times in the document", label.repr());
+            let mut error = HintedString::new(eco_format!(
+                "label `{}` occurs multiple times in the document",
+                label.repr()
+            ));
+            for &index in indices {
+                let (elem, pos) = &self.elems[index];
+                error.hint(eco_format!(
+                    "{} on page {} has this label",
+                    elem.func().name(),
+                    pos.page
+                ));
+            }
+            return Err(error);
         }
 
         Ok(&self.elems[indices[0]].0)
@@ -268,6 +775,61 @@ impl Introspector {
         }
     }
 
+    /// Query for the element matching the selector whose position on the
+    /// given page is closest to `point`.
+    ///
+    /// The distance is measured euclideanly between `point` and the element's
+    /// position (the point where the element starts). If multiple elements are
+    /// equally close, the one that comes first in the document wins, so that
+    /// the result is deterministic across layout iterations.
+    pub fn query_nearest(
+        &self,
+        selector: &Selector,
+        page: NonZeroUsize,
+        point: Point,
+    ) -> Option<Content> {
+        self.query(selector)
+            .iter()
+            .filter_map(|elem| {
+                let pos = self.position(elem.location()?);
+                (pos.page == page).then(|| ((pos.point - point).hypot(), elem))
+            })
+            .min_by_key(|&(distance, _)| distance)
+            .map(|(_, elem)| elem.clone())
+    }
+
+    /// Query for the elements matching the selector that lie within `rect` on
+    /// the given page.
+    ///
+    /// Whether an element lies within the rectangle is decided by its anchor
+    /// point (the point where the element starts), not by its bounding box,
+    /// since elements don't have a size. The rectangle's bounds are inclusive.
+    pub fn query_in_rect(
+        &self,
+        selector: &Selector,
+        page: NonZeroUsize,
+        rect: Rect,
+    ) -> EcoVec<Content> {
+        self.query(selector)
+            .iter()
+            .filter(|elem| {
+                let pos = self.position(elem.location().unwrap());
+                pos.page == page && rect.contains(pos.point)
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// All link destinations in the document alongside the positions of the
+    /// top-left corners of their areas, in document order.
+    ///
+    /// Unlike a query for `link` elements, this also covers links that were
+    /// not created by a link element, and it reports a link that is broken
+    /// across lines once per line.
+    pub fn links(&self) -> EcoVec<(Destination, Position)> {
+        self.links.iter().cloned().collect()
+    }
+
     /// The total number pages.
     pub fn pages(&self) -> NonZeroUsize {
         NonZeroUsize::new(self.pages).unwrap_or(NonZeroUsize::ONE)
@@ -294,6 +856,113 @@ impl Introspector {
             .unwrap_or(Position { page: NonZeroUsize::ONE, point: Point::zero() })
     }
 
+    /// Find the position for the given anchor, i.e. the adjusted position of
+    /// its element.
+    pub fn anchor_position(&self, anchor: Anchor) -> Position {
+        anchor.resolve(self.position(anchor.location()))
+    }
+
+    /// The nesting depth of the element at the given location.
+    ///
+    /// This is the number of boundary groups the element is nested in. A
+    /// group is a boundary if it holds an explicit box or block, or if it is
+    /// transformed, e.g. rotated or scaled. Other groups, like those of
+    /// columns, are an artifact of how frames are assembled, so they don't
+    /// count. Elements laid out directly into the page's flow, like most
+    /// headings, have depth zero.
+    pub fn depth(&self, location: Location) -> usize {
+        self.depths.get(&location).copied().unwrap_or(0)
+    }
+
+    /// Query for the elements matching the selector that are not nested in
+    /// any boundary group, i.e. those with a [depth](Self::depth) of zero.
+    pub fn roots(&self, selector: &Selector) -> EcoVec<Content> {
+        self.query(selector)
+            .iter()
+            .filter(|elem| !self.depths.contains_key(&elem.location().unwrap()))
+            .cloned()
+            .collect()
+    }
+
+    /// The bounds of the element at the given location alongside its page.
+    ///
+    /// Only explicit blocks have bounds. They are the bounding box of the
+    /// block's first frame after applying all transforms the block is nested
+    /// in. For a block that breaks across pages, this only covers its part on
+    /// the first page.
+    pub fn bounds(&self, location: Location) -> Option<(NonZeroUsize, Rect)> {
+        self.bounds.get(&location).copied()
+    }
+
+    /// Determines how the elements matching the selector changed since an
+    /// earlier [generation](Self::generation).
+    ///
+    /// Elements that match now, but didn't match back then are reported as
+    /// added and vice versa for removed elements. Elements that match in both
+    /// generations, but at a different position, are reported as moved. The
+    /// added and moved elements come first, in document order, followed by
+    /// the removed ones in their former document order.
+    ///
+    /// Only the generations retained as configured with
+    /// [`retain_history`](Self::retain_history) are known, so this returns
+    /// `None` if the generation is too old (or from the future) or if no
+    /// history is retained. Generation zero, the empty introspector, is always
+    /// known.
+    pub fn changed_since(
+        &self,
+        generation: u64,
+        selector: &Selector,
+    ) -> Option<Vec<(Location, Change)>> {
+        let empty;
+        let previous = if generation == 0 {
+            empty = Introspector::default();
+            &empty
+        } else {
+            let (_, snapshot) =
+                self.history.iter().find(|(gen, _)| *gen == generation)?;
+            snapshot.as_ref()
+        };
+
+        let before: HashMap<Location, Position> = previous
+            .query(selector)
+            .iter()
+            .map(|elem| elem.location().unwrap())
+            .map(|loc| (loc, previous.position(loc)))
+            .collect();
+        let after = self.query(selector);
+
+        let mut changes: Vec<_> = after
+            .iter()
+            .map(|elem| elem.location().unwrap())
+            .filter_map(|loc| match before.get(&loc) {
+                None => Some((loc, Change::Added)),
+                Some(&pos) if pos != self.position(loc) => Some((loc, Change::Moved)),
+                Some(_) => None,
+            })
+            .collect();
+
+        let remaining: HashSet<Location> =
+            after.iter().map(|elem| elem.location().unwrap()).collect();
+        changes.extend(
+            previous
+                .query(selector)
+                .iter()
+                .map(|elem| elem.location().unwrap())
+                .filter(|loc| !remaining.contains(loc))
+                .map(|loc| (loc, Change::Removed)),
+        );
+
+        Some(changes)
+    }
+
+    /// Find the accumulated transform for the given location.
+    ///
+    /// This combines the transforms of all groups the element is nested in,
+    /// excluding translations.
+    pub fn transform(&self, location: Location) -> Transform {
+        self.transforms.get(&location).copied().unwrap_or_default()
+    }
+
     /// Try to find a location for an element with the given `key` hash
     /// that is closest after the `anchor`.
     ///
@@ -316,26 +985,820 @@ impl Debug for Introspector {
     }
 }
 
+/// How an element changed between two generations of an introspector.
+///
+/// Returned by [`Introspector::changed_since`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Change {
+    /// The element was added or started to match.
+    Added,
+    /// The element was removed or stopped to match.
+    Removed,
+    /// The element moved to a different position.
+    Moved,
+}
+
+/// The state of an introspector, as captured by
+/// [`Introspector::snapshot`].
+#[derive(Clone)]
+pub struct IntrospectorSnapshot(Introspector);
+
 /// Caches queries.
-#[derive(Default)]
-struct QueryCache(RwLock<HashMap<u128, EcoVec<Content>>>);
+///
+/// Each entry also keeps its selector, so that the cached queries can be
+/// rerun against another introspector to find the ones that did not
+/// stabilize.
+///
+/// The cache is bounded: Once it holds
+/// [`QUERY_CACHE_CAPACITY`](Introspector::QUERY_CACHE_CAPACITY) entries, the
+/// least recently used half is evicted before inserting more. This is
+/// always correct because the cache only memoizes a pure function of the
+/// introspector's state and the selector, so an evicted query is simply
+/// recomputed on its next use. Convergence of the layout loop is unaffected as
+/// well: It is decided by validating comemo constraints against the next
+/// introspector, which reruns the queries instead of consulting this cache.
+struct QueryCache {
+    /// The cached selectors and outputs alongside the clock value of their
+    /// last use.
+    entries: RwLock<HashMap<u128, CacheEntry>>,
+    /// A logical clock that is advanced on each access.
+    clock: AtomicU64,
+    /// The hashes of the distinct queries that were performed since the
+    /// cache was last cleared. Unlike the entries, these are never evicted,
+    /// so that a query that is performed again doesn't count twice.
+    seen: Mutex<HashSet<u128>>,
+    /// Whether a query was refused since the cache was last cleared.
+    refused: AtomicBool,
+    /// The maximum number of distinct queries before queries are refused.
+    budget: usize,
+}
+
+impl Default for QueryCache {
+    fn default() -> Self {
+        Self {
+            entries: RwLock::default(),
+            clock: AtomicU64::new(0),
+            seen: Mutex::default(),
+            refused: AtomicBool::new(false),
+            budget: usize::MAX,
+        }
+    }
+}
 
 impl QueryCache {
     fn get(&self, hash: u128) -> Option<EcoVec<Content>> {
-        self.0.read().unwrap().get(&hash).cloned()
+        let entries = self.entries.read().unwrap();
+        let (_, output, used) = entries.get(&hash)?;
+        used.store(self.tick(), Ordering::Relaxed);
+        Some(output.clone())
+    }
+
+    fn insert(&self, hash: u128, selector: &Selector, output: EcoVec<Content>) {
+        let mut entries = self.entries.write().unwrap();
+        if entries.len() >= Introspector::QUERY_CACHE_CAPACITY {
+            Self::evict(&mut entries);
+        }
+        entries.insert(hash, (selector.clone(), output, AtomicU64::new(self.tick())));
     }
 
-    fn insert(&self, hash: u128, output: EcoVec<Content>) {
-        self.0.write().unwrap().insert(hash, output);
+    /// Records a query that isn't cached and returns whether it may still be
+    /// performed within the budget.
+    fn admit(&self, hash: u128) -> bool {
+        // Without a budget, there is nothing to keep track of.
+        if self.budget == usize::MAX {
+            return true;
+        }
+
+        let mut seen = self.seen.lock().unwrap();
+        if seen.contains(&hash) {
+            return true;
+        }
+
+        if seen.len() < self.budget {
+            seen.insert(hash);
+            true
+        } else {
+            self.refused.store(true, Ordering::Relaxed);
+            false
+        }
+    }
+
+    /// Whether more distinct queries were attempted than the budget allows.
+    fn exhausted(&self) -> bool {
+        self.refused.load(Ordering::Relaxed)
+    }
+
+    /// The cached selectors alongside their outputs.
+    fn queries(&self) -> Vec<(Selector, EcoVec<Content>)> {
+        self.entries
+            .read()
+            .unwrap()
+            .values()
+            .map(|(selector, output, _)| (selector.clone(), output.clone()))
+            .collect()
     }
 
     fn clear(&mut self) {
-        self.0.get_mut().unwrap().clear();
+        self.entries.get_mut().unwrap().clear();
+        self.seen.get_mut().unwrap().clear();
+        *self.refused.get_mut() = false;
+    }
+
+    /// Advance the clock and return its previous value.
+    fn tick(&self) -> u64 {
+        self.clock.fetch_add(1, Ordering::Relaxed)
+    }
+
+    /// Remove the least recently used half of the entries.
+    fn evict(entries: &mut HashMap<u128, CacheEntry>) {
+        let mut uses: Vec<u64> =
+            entries.values_mut().map(|(_, _, used)| *used.get_mut()).collect();
+        let mid = uses.len() / 2;
+        let (_, &mut threshold, _) = uses.select_nth_unstable(mid);
+        entries.retain(|_, (_, _, used)| *used.get_mut() > threshold);
     }
 }
 
+/// A cached query: Its selector, its output, and the clock value of its last
+/// use.
+type CacheEntry = (Selector, EcoVec<Content>, AtomicU64);
+
 impl Clone for QueryCache {
     fn clone(&self) -> Self {
-        Self(RwLock::new(self.0.read().unwrap().clone()))
+        let entries = self
+            .entries
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(&hash, (selector, output, used))| {
+                let used = AtomicU64::new(used.load(Ordering::Relaxed));
+                (hash, (selector.clone(), output.clone(), used))
+            })
+            .collect();
+        Self {
+            entries: RwLock::new(entries),
+            clock: AtomicU64::new(self.clock.load(Ordering::Relaxed)),
+            seen: Mutex::new(self.seen.lock().unwrap().clone()),
+            refused: AtomicBool::new(self.refused.load(Ordering::Relaxed)),
+            budget: self.budget,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::foundations::{NativeElement, TransformPredicate, Value};
+    use crate::introspection::testing::{
+        assert_converges, eval_main, page, tag, tagged, tags_page, TestWorld,
+    };
+    use crate::introspection::{MetadataElem, Tag};
+    use crate::layout::{Angle, FrameKind, GroupItem, Ratio, Size};
+    use crate::Library;
+
+    #[test]
+    fn test_introspector_links() {
+        let size = Size::new(Abs::pt(10.0), Abs::pt(5.0));
+        let url = Destination::Url("https://typst.app".into());
+        let internal = Destination::Position(Position {
+            page: NonZeroUsize::ONE,
+            point: Point::zero(),
+        });
+
+        let mut inner = Frame::soft(size);
+        inner.push(Point::zero(), FrameItem::Link(internal.clone(), size));
+
+        let mut frame = Frame::soft(size);
+        frame.push(Point::zero(), FrameItem::Link(url.clone(), size));
+        frame.push(Point::zero(), FrameItem::Link(url.clone(), size));
+        frame.push(Point::with_y(Abs::pt(5.0)), FrameItem::Group(GroupItem::new(inner)));
+
+        let mut introspector = Introspector::default();
+        introspector.rebuild(&[page(frame)]);
+
+        let links = introspector.links();
+        assert_eq!(links.len(), 2);
+        assert_eq!(links[0].0, url);
+        assert_eq!(links[1].0, internal);
+        assert_eq!(links[1].1.point, Point::with_y(Abs::pt(5.0)));
+    }
+
+    #[test]
+    fn test_introspector_generation() {
+        let mut introspector = Introspector::default();
+        introspector.rebuild(&[tags_page(&[1])]);
+        let first = introspector.generation();
+        introspector.rebuild(&[tags_page(&[1])]);
+        assert_eq!(introspector.generation(), first);
+        introspector.rebuild(&[tags_page(&[2])]);
+        assert_eq!(introspector.generation(), first + 1);
+    }
+
+    #[test]
+    fn test_introspector_changed_since() {
+        fn positioned(elems: &[(i64, f64)]) -> Page {
+            let mut frame = Frame::soft(Size::zero());
+            for &(value, y) in elems {
+                frame.push(Point::with_y(Abs::pt(y)), tag(value));
+            }
+            page(frame)
+        }
+
+        let selector = MetadataElem::elem().select();
+        let mut introspector = Introspector::default();
+        introspector.rebuild(&[positioned(&[(1, 0.0), (2, 10.0), (3, 20.0)])]);
+        let first = introspector.generation();
+
+        // Without a history, only generation zero is known.
+        assert_eq!(introspector.changed_since(first, &selector), None);
+        assert!(introspector.changed_since(0, &selector).is_some());
+
+        introspector.retain_history(2);
+        introspector.rebuild(&[positioned(&[(1, 0.0), (2, 10.0), (3, 25.0)])]);
+        introspector.rebuild(&[positioned(&[(1, 0.0), (2, 10.0), (3, 20.0)])]);
+        let first = introspector.generation();
+
+        // Element 1 is removed, 2 stays, 3 moves, and 4 is added.
+        introspector.rebuild(&[positioned(&[(2, 10.0), (4, 15.0), (3, 30.0)])]);
+        let changes = introspector.changed_since(first, &selector).unwrap();
+        assert_eq!(
+            changes,
+            [
+                (Location::new(4), Change::Added),
+                (Location::new(3), Change::Moved),
+                (Location::new(1), Change::Removed),
+            ]
+        );
+
+        let current = introspector.generation();
+        assert_eq!(introspector.changed_since(current, &selector), Some(vec![]));
+        assert_eq!(introspector.changed_since(0, &selector).unwrap().len(), 3);
+        assert_eq!(introspector.changed_since(current + 1, &selector), None);
+
+        // Old generations are forgotten eventually.
+        introspector.rebuild(&[positioned(&[(1, 0.0)])]);
+        assert_eq!(introspector.changed_since(first, &selector), None);
+        assert!(introspector.changed_since(current, &selector).is_some());
+
+        // Disabling the history forgets everything.
+        introspector.retain_history(0);
+        assert_eq!(introspector.changed_since(current, &selector), None);
+    }
+
+    #[test]
+    fn test_introspector_snapshot_restore() {
+        let selector = MetadataElem::elem().select();
+        let mut introspector = Introspector::default();
+        introspector.rebuild(&[tags_page(&[1, 2, 3])]);
+        let output = introspector.query(&selector);
+        let generation = introspector.generation();
+        let snapshot = introspector.snapshot();
+
+        // A speculative rebuild changes the results and caches its queries.
+        introspector.rebuild(&[tags_page(&[2, 4])]);
+        assert_ne!(introspector.query(&selector), output);
+        assert_eq!(introspector.cached_queries().len(), 1);
+        assert_ne!(introspector.generation(), generation);
+
+        introspector.restore(snapshot);
+        assert_eq!(introspector.generation(), generation);
+        assert_eq!(introspector.cached_queries(), [(selector.clone(), output.clone())]);
+        assert_eq!(introspector.query(&selector), output);
+        assert_eq!(
+            introspector.position(Location::new(3)).point,
+            Point::with_y(Abs::pt(3.0))
+        );
+        assert_eq!(
+            introspector
+                .anchor_position(Location::new(3).offset(Abs::pt(1.0), Abs::pt(2.0))),
+            Position {
+                page: NonZeroUsize::ONE,
+                point: Point::new(Abs::pt(1.0), Abs::pt(5.0))
+            }
+        );
+    }
+
+    #[test]
+    fn test_introspector_elem_in() {
+        use crate::model::{HeadingElem, StrongElem};
+
+        let elems = [
+            MetadataElem::new(Value::None).pack(),
+            StrongElem::new(Content::empty()).pack(),
+            HeadingElem::new(Content::empty()).pack(),
+            MetadataElem::new(Value::None).pack(),
+        ];
+
+        let mut frame = Frame::soft(Size::zero());
+        for (i, elem) in elems.into_iter().enumerate() {
+            frame.push(Point::with_y(Abs::pt(i as f64)), tagged(elem, i as u128));
+        }
+
+        let mut introspector = Introspector::default();
+        introspector.rebuild(&[page(frame)]);
+
+        let hashes = |elems: &EcoVec<Content>| -> Vec<u128> {
+            elems.iter().map(|elem| elem.location().unwrap().hash()).collect()
+        };
+
+        let selector = Selector::elem_in([HeadingElem::elem(), MetadataElem::elem()]);
+        assert_eq!(hashes(&introspector.query(&selector)), [0, 2, 3]);
+        assert_eq!(
+            crate::utils::hash128(&selector),
+            crate::utils::hash128(&Selector::elem_in([
+                MetadataElem::elem(),
+                HeadingElem::elem(),
+                MetadataElem::elem(),
+            ])),
+        );
+
+        // Element sets are matched in the same pass as other element
+        // selectors.
+        let selectors = [
+            selector,
+            Selector::elem_in(std::iter::empty()),
+            StrongElem::elem().select(),
+        ];
+        let many: Vec<_> =
+            introspector.query_many(&selectors).iter().map(hashes).collect();
+        assert_eq!(many, [vec![0, 2, 3], vec![], vec![1]]);
+    }
+
+    #[test]
+    fn test_introspector_map_meta() {
+        let old = Label::new("old");
+        let new = Label::new("new");
+        let loc = Location::new(1);
+        let elem = MetadataElem::new(Value::None).pack().labelled(old).located(loc);
+
+        let mut inner = Frame::soft(Size::zero());
+        inner.push(Point::with_x(Abs::pt(5.0)), FrameItem::Tag(Tag::new(elem, 1)));
+        let mut group = GroupItem::new(inner);
+        group.transform = Transform::scale(Ratio::new(2.0), Ratio::one());
+        let mut frame = Frame::soft(Size::zero());
+        frame.push(Point::with_y(Abs::pt(3.0)), FrameItem::Group(group));
+
+        frame.map_meta(|tag| {
+            if tag.elem.label() == Some(old) {
+                tag.elem.set_label(new);
+            }
+        });
+
+        let mut introspector = Introspector::default();
+        introspector.rebuild(&[page(frame)]);
+        assert!(introspector.query(&Selector::Label(old)).is_empty());
+        assert_eq!(introspector.query(&Selector::Label(new)).len(), 1);
+        assert_eq!(
+            introspector.position(loc).point,
+            Point::new(Abs::pt(10.0), Abs::pt(3.0))
+        );
+    }
+
+    #[test]
+    fn test_introspector_transform_selector() {
+        fn group(frame: Frame, transform: Transform) -> FrameItem {
+            FrameItem::Group(GroupItem { transform, ..GroupItem::new(frame) })
+        }
+
+        let rotate = Transform::rotate(Angle::deg(90.0));
+        let unrotate = Transform::rotate(Angle::deg(-90.0));
+        let half = Transform::scale(Ratio::new(0.5), Ratio::new(0.5));
+        let moved = Transform::translate(Abs::pt(5.0), Abs::pt(5.0));
+
+        // A rotated group nested in a moved group.
+        let mut rotated = Frame::soft(Size::zero());
+        rotated.push(Point::zero(), tag(2));
+        let mut outer = Frame::soft(Size::zero());
+        outer.push(Point::zero(), tag(1));
+        outer.push(Point::zero(), group(rotated, rotate));
+
+        // A scaled group nested in groups whose rotations cancel out.
+        let mut scaled = Frame::soft(Size::zero());
+        scaled.push(Point::zero(), tag(4));
+        let mut inner = Frame::soft(Size::zero());
+        inner.push(Point::zero(), tag(3));
+        inner.push(Point::zero(), group(scaled, half));
+        let mut unrotated = Frame::soft(Size::zero());
+        unrotated.push(Point::zero(), group(inner, unrotate));
+
+        let mut frame = Frame::soft(Size::zero());
+        frame.push(Point::zero(), group(outer, moved));
+        frame.push(Point::zero(), group(unrotated, rotate));
+
+        let mut introspector = Introspector::default();
+        introspector.rebuild(&[page(frame)]);
+
+        let select = |predicate| {
+            let selector = Selector::Transform {
+                selector: MetadataElem::elem().select().into(),
+                predicate,
+            };
+            introspector
+                .query(&selector)
+                .iter()
+                .map(|elem| elem.location().unwrap())
+                .collect::<Vec<_>>()
+        };
+
+        assert!(introspector.transform(Location::new(1)).is_identity());
+        assert_eq!(select(TransformPredicate::Rotated), [Location::new(2)]);
+        assert_eq!(
+            select(TransformPredicate::ScaledBelow(Ratio::one())),
+            [Location::new(4)]
+        );
+        assert!(select(TransformPredicate::ScaledBelow(Ratio::new(0.5))).is_empty());
+    }
+
+    #[test]
+    fn test_extract_meta() {
+        let mut inner = Frame::soft(Size::zero());
+        inner.push(Point::with_x(Abs::pt(2.0)), tag(1));
+        let mut frame = Frame::soft(Size::zero());
+        frame.push(Point::with_y(Abs::pt(3.0)), FrameItem::Group(GroupItem::new(inner)));
+        frame.push(Point::zero(), tag(1));
+
+        let ts = Transform::translate(Abs::pt(1.0), Abs::zero());
+        let meta = extract_meta(&frame, NonZeroUsize::ONE, ts);
+        assert_eq!(meta.len(), 1);
+        assert_eq!(meta[0].location, Location::new(1));
+        assert_eq!(meta[0].position.point, Point::new(Abs::pt(3.0), Abs::pt(3.0)));
+        assert_eq!((meta[0].transform, meta[0].depth), (Transform::identity(), 0));
+    }
+
+    #[test]
+    fn test_introspector_query_nearest() {
+        let point = |x, y| Point::new(Abs::pt(x), Abs::pt(y));
+        let mut first = Frame::soft(Size::zero());
+        first.push(point(0.0, 0.0), tag(1));
+        first.push(point(10.0, 0.0), tag(2));
+        first.push(point(0.0, 10.0), tag(3));
+        let mut second = Frame::soft(Size::zero());
+        second.push(point(4.0, 0.0), tag(4));
+
+        let mut introspector = Introspector::default();
+        introspector.rebuild(&[page(first), page(second)]);
+
+        let selector = MetadataElem::elem().select();
+        let nearest = |page, x, y| {
+            introspector
+                .query_nearest(&selector, page, point(x, y))
+                .map(|elem| elem.location().unwrap())
+        };
+
+        let one = NonZeroUsize::ONE;
+        assert_eq!(nearest(one, 6.0, 1.0), Some(Location::new(2)));
+        assert_eq!(nearest(one, 1.0, 6.0), Some(Location::new(3)));
+
+        // Elements on other pages are ignored, even if they are closer.
+        assert_eq!(nearest(one, 4.0, 0.0), Some(Location::new(1)));
+
+        // Ties are broken by document order.
+        assert_eq!(nearest(one, 5.0, 0.0), Some(Location::new(1)));
+        assert_eq!(nearest(NonZeroUsize::new(3).unwrap(), 0.0, 0.0), None);
+    }
+
+    #[test]
+    fn test_introspector_query_in_rect() {
+        let point = |x, y| Point::new(Abs::pt(x), Abs::pt(y));
+        let mut first = Frame::soft(Size::zero());
+        first.push(point(1.0, 1.0), tag(1));
+        first.push(point(5.0, 5.0), tag(2));
+        first.push(point(9.0, 9.0), tag(3));
+        let mut second = Frame::soft(Size::zero());
+        second.push(point(5.0, 5.0), tag(4));
+
+        let mut introspector = Introspector::default();
+        introspector.rebuild(&[page(first), page(second)]);
+
+        let selector = MetadataElem::elem().select();
+        let rect = Rect::new(point(1.0, 1.0), point(6.0, 6.0));
+        let found: Vec<_> = introspector
+            .query_in_rect(&selector, NonZeroUsize::ONE, rect)
+            .iter()
+            .map(|elem| elem.location().unwrap())
+            .collect();
+        assert_eq!(found, [Location::new(1), Location::new(2)]);
+    }
+
+    #[test]
+    fn test_introspector_depth() {
+        // Soft and plain hard groups are no boundaries, but containers and
+        // transformed groups are.
+        let mut boxed = Frame::new(Size::zero(), FrameKind::Container);
+        boxed.push(Point::zero(), tag(3));
+        let mut hard = Frame::hard(Size::zero());
+        hard.push(Point::zero(), tag(2));
+        hard.push(Point::zero(), FrameItem::Group(GroupItem::new(boxed)));
+
+        let mut nested = Frame::new(Size::zero(), FrameKind::Container);
+        nested.push(Point::zero(), tag(4));
+        let mut inner = Frame::soft(Size::zero());
+        inner.push(Point::zero(), FrameItem::Group(GroupItem::new(nested)));
+        let mut rotated = GroupItem::new(inner);
+        rotated.transform = Transform::rotate(Angle::deg(90.0));
+
+        let mut frame = Frame::soft(Size::zero());
+        frame.push(Point::zero(), tag(1));
+        frame.push(Point::zero(), FrameItem::Group(GroupItem::new(hard)));
+        frame.push(Point::zero(), FrameItem::Group(rotated));
+
+        let mut introspector = Introspector::default();
+        introspector.rebuild(&[page(frame)]);
+
+        let depth = |value| introspector.depth(Location::new(value));
+        assert_eq!([1, 2, 3, 4].map(depth), [0, 0, 1, 2]);
+
+        let roots: Vec<_> = introspector
+            .roots(&MetadataElem::elem().select())
+            .iter()
+            .map(|elem| elem.location().unwrap())
+            .collect();
+        assert_eq!(roots, [Location::new(1), Location::new(2)]);
+    }
+
+    #[test]
+    fn test_introspector_depth_columns() {
+        // Columns are laid out into a hard frame, but aren't a boundary.
+        let world = TestWorld::new(
+            "#set page(columns: 2)\n\
+             #metadata(1)\n\
+             #box(metadata(2))\n\
+             #block(box(metadata(3)))",
+        );
+        let doc = crate::compile(&world).output.unwrap();
+        let introspector = &doc.introspector;
+
+        let selector = MetadataElem::elem().select();
+        let depths: Vec<_> = introspector
+            .query(&selector)
+            .iter()
+            .map(|elem| introspector.depth(elem.location().unwrap()))
+            .collect();
+        assert_eq!(depths, [0, 1, 2]);
+        assert_eq!(introspector.roots(&selector).len(), 1);
+    }
+
+    #[test]
+    fn test_introspector_descendant_of() {
+        let world = TestWorld::new(
+            "#block(width: 50pt)[\n\
+               A #metadata(1)\n\
+               #block[B #metadata(2)] <inner>\n\
+             ] <thm>\n\
+             C #metadata(3)\n\n\
+             #metadata(4)\n\
+             #block(width: 50pt)[D #metadata(5)] <other>",
+        );
+        let doc = crate::compile(&world).output.unwrap();
+        let introspector = &doc.introspector;
+
+        let values = |selector: Selector| -> Vec<Value> {
+            introspector
+                .query(&selector)
+                .iter()
+                .map(|elem| elem.to_packed::<MetadataElem>().unwrap().value.clone())
+                .collect()
+        };
+        let within = |label: &str| {
+            values(Selector::DescendantOf {
+                ancestor: Arc::new(Selector::Label(Label::new(label))),
+                descendant: Arc::new(MetadataElem::elem().select()),
+            })
+        };
+        assert_eq!(within("thm"), [Value::Int(1), Value::Int(2)]);
+        assert_eq!(within("inner"), [Value::Int(2)]);
+
+        // The metadata right before the block is no block, so it doesn't get
+        // the block's bounds.
+        let other = introspector.query_label(Label::new("other")).unwrap();
+        let before = introspector
+            .query(&MetadataElem::elem().select())
+            .iter()
+            .find(|elem| elem.to_packed::<MetadataElem>().unwrap().value == Value::Int(4))
+            .unwrap()
+            .clone();
+        assert!(introspector.bounds(other.location().unwrap()).is_some());
+        assert_eq!(introspector.bounds(before.location().unwrap()), None);
+    }
+
+    #[test]
+    fn test_introspector_converges() {
+        // A state and a counter that feed their final values back into
+        // themselves until they reach a fixed point.
+        for text in [
+            "#let s = state(\"s\", 0)\n\
+             #context s.update(calc.min(s.final() + 1, 3))",
+            "#let c = counter(\"c\")\n\
+             #context for _ in range(calc.min(c.final().first() + 1, 3)) { c.step() }",
+        ] {
+            let world = TestWorld::new(text);
+            assert_converges(&world, &eval_main(&world), 5);
+        }
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "the query for `state-update.where(key: \"s\")` kept changing"
+    )]
+    fn test_introspector_diverges() {
+        let world =
+            TestWorld::new("#let s = state(\"s\", 0)\n#context s.update(s.final() + 1)");
+        assert_converges(&world, &eval_main(&world), 5);
+    }
+
+    #[test]
+    fn test_introspector_co_located_order() {
+        // Elements at the same position, whose locations are not ordered
+        // like the elements. One of them occurs twice.
+        let values = [(30, "c"), (10, "a"), (20, "b"), (10, "a")];
+        let mut frame = Frame::soft(Size::zero());
+        for (hash, value) in values {
+            let elem = MetadataElem::new(Value::Str(value.into())).pack();
+            frame.push(Point::zero(), tagged(elem, hash));
+        }
+
+        let pages = [page(frame)];
+        let mut introspector = Introspector::default();
+        let order = |introspector: &Introspector| -> Vec<_> {
+            introspector
+                .query(&MetadataElem::elem().select())
+                .iter()
+                .map(|elem| elem.location().unwrap().hash())
+                .collect()
+        };
+
+        introspector.rebuild(&pages);
+        assert_eq!(order(&introspector), [30, 10, 20]);
+        introspector.rebuild(&pages);
+        assert_eq!(order(&introspector), [30, 10, 20]);
+    }
+
+    #[test]
+    fn test_introspector_first_last_location() {
+        // Marked elements are scattered across three pages, with unmarked ones
+        // before, between and after them.
+        let marker = Label::new("marker");
+        let page = |values: &[(u128, bool)]| {
+            let mut frame = Frame::soft(Size::zero());
+            for &(value, marked) in values {
+                let mut elem = MetadataElem::new(Value::None).pack();
+                if marked {
+                    elem.set_label(marker);
+                }
+                frame.push(Point::zero(), tagged(elem, value));
+            }
+            page(frame)
+        };
+
+        let mut introspector = Introspector::default();
+        introspector.rebuild(&[
+            page(&[(1, false), (2, false)]),
+            page(&[(3, true), (4, false), (5, true)]),
+            page(&[(6, false)]),
+            page(&[(7, true), (8, false)]),
+        ]);
+
+        let selector = Selector::Label(marker);
+        assert_eq!(introspector.first_location(&selector), Some(Location::new(3)));
+        assert_eq!(introspector.last_location(&selector), Some(Location::new(7)));
+        assert_eq!(introspector.page(Location::new(7)).get(), 4);
+
+        let all = MetadataElem::elem().select();
+        assert_eq!(introspector.first_location(&all), Some(Location::new(1)));
+        assert_eq!(introspector.last_location(&all), Some(Location::new(8)));
+
+        let none = Selector::Label(Label::new("missing"));
+        assert_eq!(introspector.first_location(&none), None);
+        assert_eq!(introspector.last_location(&none), None);
+    }
+
+    #[test]
+    fn test_introspector_unstable_key() {
+        fn keyed(locs: &[(u128, u128)]) -> Page {
+            let mut frame = Frame::soft(Size::zero());
+            for &(key, loc) in locs {
+                let loc = Location::new(loc);
+                let elem = MetadataElem::new(Value::None).pack().located(loc);
+                frame.push(Point::zero(), FrameItem::Tag(Tag::new(elem, key)));
+            }
+            page(frame)
+        }
+
+        let mut introspector = Introspector::default();
+        introspector.rebuild(&[keyed(&[(1, 10), (2, 20)])]);
+        let previous = introspector.key_locations();
+
+        introspector.rebuild(&[keyed(&[(1, 10), (2, 20)])]);
+        assert_eq!(introspector.unstable_key(&previous), None);
+
+        introspector.rebuild(&[keyed(&[(1, 10), (2, 30)])]);
+        assert_eq!(introspector.unstable_key(&previous), Some(2));
+
+        // Elements with the same key may swap their order.
+        introspector.rebuild(&[keyed(&[(1, 10), (1, 11), (2, 20)])]);
+        let previous = introspector.key_locations();
+        introspector.rebuild(&[keyed(&[(1, 11), (1, 10), (2, 20)])]);
+        assert_eq!(introspector.unstable_key(&previous), None);
+
+        introspector.rebuild(&[keyed(&[(1, 10), (3, 30)])]);
+        assert_eq!(introspector.unstable_key(&previous), None);
+    }
+
+    #[test]
+    fn test_introspector_to_json() {
+        let mut introspector = Introspector::default();
+        introspector.rebuild(&[tags_page(&[2, 1])]);
+
+        let json = introspector.to_json().unwrap();
+        let entries = json.as_array().unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0]["location"], format!("{:032x}", 1));
+        assert_eq!(entries[0]["page"], 1);
+        assert_eq!(entries[0]["y"], 1.0);
+        assert_eq!(entries[0]["element"]["func"], "metadata");
+        assert_eq!(entries[0]["element"]["value"], 1);
+        assert_eq!(entries[1]["element"]["value"], 2);
+    }
+
+    #[test]
+    fn test_introspector_query_many() {
+        let page = tags_page(&[1, 2, 3]);
+        let mut batched = Introspector::default();
+        batched.rebuild(std::slice::from_ref(&page));
+        let mut single = Introspector::default();
+        single.rebuild(&[page]);
+
+        let metadata = MetadataElem::elem();
+        let selectors = [
+            metadata.select(),
+            Selector::Elem(
+                metadata,
+                Some(smallvec::smallvec![(
+                    metadata.field_id("value").unwrap(),
+                    Value::Int(2)
+                )]),
+            ),
+            Selector::Location(Location::new(3)),
+        ];
+        let expected: Vec<_> = selectors.iter().map(|sel| single.query(sel)).collect();
+        assert_eq!(batched.query_many(&selectors), expected);
+        assert_eq!(expected[1].len(), 1);
+    }
+
+    #[test]
+    fn test_introspector_query_budget() {
+        let mut introspector = Introspector::default();
+        introspector.set_query_budget(2);
+        introspector.rebuild(&[tags_page(&[1, 2, 3])]);
+
+        // Cached queries don't count, nested ones do.
+        let at = |hash| Selector::Location(Location::new(hash));
+        assert_eq!(introspector.query(&at(1)).len(), 1);
+        assert_eq!(introspector.query(&at(1)).len(), 1);
+        assert!(!introspector.query_budget_exceeded());
+        let before = Selector::Before {
+            selector: Arc::new(MetadataElem::elem().select()),
+            end: Arc::new(at(2)),
+            inclusive: true,
+        };
+        assert!(introspector.query(&before).is_empty());
+        assert!(introspector.query_budget_exceeded());
+
+        // The budget applies anew after a rebuild.
+        introspector.rebuild(&[tags_page(&[1, 2, 3])]);
+        assert!(!introspector.query_budget_exceeded());
+        assert_eq!(introspector.query(&at(3)).len(), 1);
+
+        // A query that was evicted from the cache doesn't count again, and
+        // neither does a selector that is passed to `query_many` twice.
+        let capacity = Introspector::QUERY_CACHE_CAPACITY;
+        let metadata = MetadataElem::elem().select();
+        introspector.set_query_budget(capacity + 2);
+        introspector.rebuild(&[tags_page(&[1, 2, 3])]);
+        for hash in 0..=capacity {
+            introspector.query(&at(hash as u128));
+        }
+        introspector.query(&at(0));
+        introspector.query_many(&[metadata.clone(), metadata]);
+        assert!(!introspector.query_budget_exceeded());
+        introspector.query(&at(capacity as u128 + 1));
+        assert!(introspector.query_budget_exceeded());
+    }
+
+    #[test]
+    fn test_compile_query_budget() {
+        let compile = |text: &str| {
+            let library = Library::builder().with_max_queries(20).build();
+            crate::compile(&TestWorld::with_library(text, library)).output
+        };
+
+        // Performing the same query over and over counts once.
+        assert!(compile("#for _ in range(50) { context query(<a>).len() }").is_ok());
+
+        // Distinct queries count separately.
+        let errors =
+            compile("#for i in range(50) { context query(label(str(i))).len() }")
+                .unwrap_err();
+        assert_eq!(errors[0].message, "layout performed too many distinct queries");
     }
 }
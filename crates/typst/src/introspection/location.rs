@@ -1,11 +1,13 @@
 use std::fmt::{self, Debug, Formatter};
 use std::num::NonZeroUsize;
 
-use ecow::EcoString;
+use ecow::{eco_format, EcoString};
 
 use crate::engine::Engine;
-use crate::foundations::{func, scope, ty, Repr};
-use crate::layout::Position;
+use crate::foundations::{
+    dict, func, scope, ty, Dict, FromValue, IntoValue, Repr, Value,
+};
+use crate::layout::{Abs, Length, Point, Position};
 use crate::model::Numbering;
 
 /// Identifies an element in the document.
@@ -44,8 +46,157 @@ impl Location {
     pub fn variant(self, n: usize) -> Self {
         Self(crate::utils::hash128(&(self.0, n)))
     }
+
+    /// Derives an anchor that is moved by the given offsets relative to the
+    /// position of this location's element.
+    pub fn offset(self, dx: Abs, dy: Abs) -> Anchor {
+        Anchor::from(self).offset(dx, dy)
+    }
+
+    /// Derives an anchor with the same coordinates as this location's
+    /// element, but on the given page.
+    pub fn with_page(self, page: NonZeroUsize) -> Anchor {
+        Anchor::from(self).with_page(page)
+    }
+
+    /// Encodes this location into a dictionary.
+    ///
+    /// The hash does not fit into an integer, so it is stored as a hexadecimal
+    /// string under the `loc` key. Use [`decode`](Self::decode) to recover the
+    /// location.
+    pub fn encode(self) -> Dict {
+        dict! { "loc" => eco_format!("{:032x}", self.0) }
+    }
+
+    /// Recovers a location from a dictionary produced by
+    /// [`encode`](Self::encode) or [`Anchor::encode`].
+    ///
+    /// Returns `None` if the dictionary does not contain a valid location.
+    pub fn decode(dict: &Dict) -> Option<Self> {
+        let Value::Str(hex) = dict.get("loc").ok()? else { return None };
+        u128::from_str_radix(hex.as_str(), 16).ok().map(Self)
+    }
+
+    /// Encodes this location into a short string of lowercase letters and
+    /// digits, which can be used as-is in a URL, e.g. in its fragment.
+    ///
+    /// The encoding is the hash in base 36 and takes at most 25 characters.
+    /// Use [`from_base36`](Self::from_base36) to recover the location.
+    pub fn to_base36(self) -> EcoString {
+        let mut digits = Vec::with_capacity(25);
+        let mut hash = self.0;
+        loop {
+            digits.push(BASE36_DIGITS[(hash % 36) as usize]);
+            hash /= 36;
+            if hash == 0 {
+                break;
+            }
+        }
+        digits.iter().rev().map(|&digit| digit as char).collect()
+    }
+
+    /// Recovers a location from a string produced by
+    /// [`to_base36`](Self::to_base36).
+    ///
+    /// Only accepts exactly the strings that `to_base36` produces, so each
+    /// location has a single encoding. Returns `None` for anything else, e.g.
+    /// for uppercase letters, leading zeros, or values that overflow.
+    pub fn from_base36(string: &str) -> Option<Self> {
+        let canonical = !string.is_empty()
+            && (string == "0" || !string.starts_with('0'))
+            && string.bytes().all(|c| BASE36_DIGITS.contains(&c));
+        if !canonical {
+            return None;
+        }
+        u128::from_str_radix(string, 36).ok().map(Self)
+    }
+}
+
+/// A position relative to the position of a located element.
+///
+/// Anchors are derived from a location with [`Location::offset`] and
+/// [`Location::with_page`] and resolved with
+/// [`Introspector::anchor_position`](crate::introspection::Introspector::anchor_position).
+/// They are not locations themselves: The
+/// element's location stays the same, only the resolved position is adjusted.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Anchor {
+    /// The location of the element the anchor is relative to.
+    location: Location,
+    /// The page to move the position to.
+    page: Option<NonZeroUsize>,
+    /// The offset to move the position by.
+    delta: Point,
+}
+
+impl Anchor {
+    /// The location of the element the anchor is relative to.
+    pub fn location(self) -> Location {
+        self.location
+    }
+
+    /// Moves the anchor by the given offsets.
+    pub fn offset(self, dx: Abs, dy: Abs) -> Self {
+        Self { delta: self.delta + Point::new(dx, dy), ..self }
+    }
+
+    /// Moves the anchor to the given page, keeping its coordinates.
+    pub fn with_page(self, page: NonZeroUsize) -> Self {
+        Self { page: Some(page), ..self }
+    }
+
+    /// Applies the anchor's adjustments to the position of its element.
+    pub fn resolve(self, pos: Position) -> Position {
+        Position {
+            page: self.page.unwrap_or(pos.page),
+            point: pos.point + self.delta,
+        }
+    }
+
+    /// Encodes this anchor into a dictionary.
+    ///
+    /// Extends the [encoded location](Location::encode) with the adjustments
+    /// under the `page`, `dx` and `dy` keys, if present. Use
+    /// [`decode`](Self::decode) to recover the anchor.
+    pub fn encode(self) -> Dict {
+        let mut dict = self.location.encode();
+        if let Some(page) = self.page {
+            dict.insert("page".into(), page.into_value());
+        }
+        if self.delta != Point::zero() {
+            dict.insert("dx".into(), self.delta.x.into_value());
+            dict.insert("dy".into(), self.delta.y.into_value());
+        }
+        dict
+    }
+
+    /// Recovers an anchor from a dictionary produced by
+    /// [`encode`](Self::encode) or [`Location::encode`].
+    ///
+    /// Returns `None` if the dictionary does not contain a valid anchor.
+    pub fn decode(dict: &Dict) -> Option<Self> {
+        let location = Location::decode(dict)?;
+        let field = |key: &str| dict.get(key).ok().cloned();
+        let page = field("page").map(NonZeroUsize::from_value).transpose().ok()?;
+        let dx = field("dx").map(Length::from_value).transpose().ok()?;
+        let dy = field("dy").map(Length::from_value).transpose().ok()?;
+        let delta = Point::new(
+            dx.map_or(Abs::zero(), |dx| dx.abs),
+            dy.map_or(Abs::zero(), |dy| dy.abs),
+        );
+        Some(Self { location, page, delta })
+    }
+}
+
+impl From<Location> for Anchor {
+    fn from(location: Location) -> Self {
+        Self { location, page: None, delta: Point::zero() }
+    }
 }
 
+/// The digits used by [`Location::to_base36`].
+const BASE36_DIGITS: &[u8; 36] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
 #[scope]
 impl Location {
     /// Returns the page number for this location.
@@ -107,3 +258,77 @@ impl Repr for Location {
 
 /// Makes this element locatable through `engine.locate`.
 pub trait Locatable {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_location_encode_decode() {
+        for hash in [0, 1, 0xdead_beef, u128::MAX] {
+            let loc = Location::new(hash);
+            assert_eq!(Location::decode(&loc.encode()), Some(loc));
+        }
+
+        assert_eq!(Location::decode(&Dict::new()), None);
+        assert_eq!(Location::decode(&dict! { "loc" => 5 }), None);
+        assert_eq!(Location::decode(&dict! { "loc" => "xyz" }), None);
+    }
+
+    #[test]
+    fn test_location_anchor() {
+        let loc = Location::new(7);
+        let pos = Position {
+            page: NonZeroUsize::new(2).unwrap(),
+            point: Point::new(Abs::pt(10.0), Abs::pt(20.0)),
+        };
+
+        let anchor = loc
+            .offset(Abs::pt(5.0), Abs::pt(-10.0))
+            .offset(Abs::zero(), Abs::pt(-20.0))
+            .with_page(NonZeroUsize::MIN);
+        assert_eq!(anchor.location(), loc);
+        assert_eq!(Anchor::from(loc).resolve(pos), pos);
+
+        let resolved = anchor.resolve(pos);
+        assert_eq!(resolved.page, NonZeroUsize::MIN);
+        assert_eq!(resolved.point, Point::new(Abs::pt(15.0), Abs::pt(-10.0)));
+
+        // Anchors round-trip through their encoded dictionary, which extends
+        // the one of their location.
+        assert_eq!(Anchor::decode(&anchor.encode()), Some(anchor));
+        assert_eq!(Location::decode(&anchor.encode()), Some(loc));
+        assert_eq!(Anchor::decode(&loc.encode()), Some(Anchor::from(loc)));
+        assert_eq!(Anchor::from(loc).encode(), loc.encode());
+        assert_eq!(Anchor::decode(&dict! { "loc" => "7", "page" => 0 }), None);
+    }
+
+    #[test]
+    fn test_location_base36() {
+        for hash in [0, 1, 35, 36, 0xdead_beef, u128::MAX - 1, u128::MAX] {
+            let loc = Location::new(hash);
+            let encoded = loc.to_base36();
+            assert!(encoded.len() <= 25);
+            assert_eq!(Location::from_base36(&encoded), Some(loc));
+        }
+
+        assert_eq!(Location::new(0).to_base36(), "0");
+        assert_eq!(Location::new(71).to_base36(), "1z");
+        assert_eq!(Location::new(u128::MAX).to_base36(), "f5lxx1zz5pnorynqglhzmsp33");
+
+        for malformed in [
+            "",
+            "+1",
+            "-1",
+            "1Z",
+            "01",
+            "00",
+            "1 ",
+            "1_0",
+            "ä",
+            "zzzzzzzzzzzzzzzzzzzzzzzzz",
+        ] {
+            assert_eq!(Location::from_base36(malformed), None);
+        }
+    }
+}
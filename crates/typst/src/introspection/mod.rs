@@ -12,6 +12,8 @@ mod metadata;
 #[path = "query.rs"]
 mod query_;
 mod state;
+#[cfg(test)]
+pub(crate) mod testing;
 
 pub use self::counter::*;
 pub use self::here_::*;
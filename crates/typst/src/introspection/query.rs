@@ -69,6 +69,13 @@ use crate::introspection::Location;
 /// counter]($counter/#page-counter) at that location and apply the numbering to
 /// the counter.
 ///
+/// Queries find all elements that are laid out, even if they are not visible
+/// in the output: Elements that are [hidden]($hide), clipped away, or placed
+/// outside of the page are found, too. The [position]($location.position) of
+/// such an element may lie outside of its page. Content that is only
+/// [measured]($measure), on the other hand, never becomes part of the document
+/// and is thus not found.
+///
 /// # A word of caution { #caution }
 /// To resolve all your queries, Typst evaluates and layouts parts of the
 /// document multiple times. However, there is no guarantee that your queries
@@ -0,0 +1,216 @@
+//! Fixtures for tests that introspect hand-built frames or documents.
+
+use comemo::{Track, Validate};
+use once_cell::sync::Lazy;
+
+use crate::diag::{FileError, FileResult};
+use crate::engine::{Engine, Route, Sink, Traced};
+use crate::foundations::{
+    Bytes, Content, Datetime, NativeElement, Repr, StyleChain, Value,
+};
+use crate::introspection::{Introspector, Location, MetadataElem, Tag};
+use crate::layout::{Abs, Frame, FrameItem, Page, Point, Size};
+use crate::syntax::{FileId, Source};
+use crate::text::{Font, FontBook};
+use crate::utils::LazyHash;
+use crate::{Library, World};
+
+/// A tag for a metadata element with the given value, located at
+/// `Location::new(value)`.
+pub fn tag(value: i64) -> FrameItem {
+    tagged(MetadataElem::new(Value::Int(value)).pack(), value as u128)
+}
+
+/// A tag for the given element, located at `Location::new(hash)`.
+pub fn tagged(elem: Content, hash: u128) -> FrameItem {
+    let loc = Location::new(hash);
+    FrameItem::Tag(Tag::new(elem.located(loc), loc.hash()))
+}
+
+/// A page consisting of the given frame.
+pub fn page(frame: Frame) -> Page {
+    Page { frame, numbering: None, number: 1 }
+}
+
+/// A page with a [`tag`] for each value, placed as many points from the top
+/// as the value.
+pub fn tags_page(values: &[i64]) -> Page {
+    let mut frame = Frame::soft(Size::zero());
+    for &value in values {
+        frame.push(Point::with_y(Abs::pt(value as f64)), tag(value));
+    }
+    page(frame)
+}
+
+/// Runs `f` on an engine that introspects with the given introspector.
+///
+/// The engine's world is a [`TestWorld`] without source text and its sink is
+/// discarded afterwards.
+pub fn with_engine<T>(
+    introspector: &Introspector,
+    f: impl FnOnce(&mut Engine) -> T,
+) -> T {
+    let world = TestWorld::new("");
+    let traced = Traced::default();
+    let mut sink = Sink::new();
+    let mut engine = Engine {
+        world: (&world as &dyn World).track(),
+        introspector: introspector.track(),
+        traced: traced.track(),
+        sink: sink.track_mut(),
+        route: Route::default(),
+    };
+    f(&mut engine)
+}
+
+/// Evaluates the main source file of the world into content.
+#[track_caller]
+pub fn eval_main(world: &dyn World) -> Content {
+    let traced = Traced::default();
+    let mut sink = Sink::new();
+    crate::eval::eval(
+        world.track(),
+        traced.track(),
+        sink.track_mut(),
+        Route::default().track(),
+        &world.main(),
+    )
+    .expect("evaluation failed")
+    .content()
+}
+
+/// Lays out the content like [`compile`](crate::compile) does until
+/// introspection stabilizes and panics if that takes more than `max_iters`
+/// layout passes.
+///
+/// The panic message names a query whose results kept changing between the
+/// last two passes, if there is one.
+#[track_caller]
+pub fn assert_converges(world: &dyn World, content: &Content, max_iters: usize) {
+    let library = world.library();
+    let styles = StyleChain::new(&library.styles);
+    let traced = Traced::default();
+    let mut sink = Sink::new();
+    let mut introspector = Introspector::default();
+    let mut queries = vec![];
+
+    for _ in 0..max_iters {
+        sink.delayed();
+        sink.invalidated();
+
+        let constraint = <Introspector as Validate>::Constraint::new();
+        let mut engine = Engine {
+            world: world.track(),
+            introspector: introspector.track_with(&constraint),
+            traced: traced.track(),
+            sink: sink.track_mut(),
+            route: Route::default(),
+        };
+
+        let mut next =
+            content.layout_document(&mut engine, styles).expect("layout failed");
+        next.introspector = std::mem::take(&mut introspector);
+        queries = next.introspector.cached_queries();
+        next.introspector.rebuild(&next.pages);
+        introspector = next.introspector;
+
+        if introspector.validate(&constraint) && !sink.invalidated() {
+            return;
+        }
+    }
+
+    match introspector.unstable_query(&queries) {
+        Some(selector) => panic!(
+            "layout did not converge within {max_iters} passes, \
+             the results of the query for `{}` kept changing",
+            selector.repr(),
+        ),
+        None => panic!("layout did not converge within {max_iters} passes"),
+    }
+}
+
+/// A world with a single detached source file and the development fonts.
+pub struct TestWorld {
+    main: Source,
+    base: &'static TestBase,
+    /// A library that replaces the standard library, if any.
+    library: Option<LazyHash<Library>>,
+}
+
+impl TestWorld {
+    /// Create a new world with the given source text.
+    ///
+    /// This is cheap because the shared base is lazily initialized just once.
+    pub fn new(text: &str) -> Self {
+        static BASE: Lazy<TestBase> = Lazy::new(TestBase::default);
+        Self {
+            main: Source::detached(text),
+            base: &*BASE,
+            library: None,
+        }
+    }
+
+    /// Create a new world with the given source text and library, e.g. one
+    /// with additional functions or a different configuration.
+    pub fn with_library(text: &str, library: Library) -> Self {
+        Self {
+            library: Some(LazyHash::new(library)),
+            ..Self::new(text)
+        }
+    }
+}
+
+impl World for TestWorld {
+    fn library(&self) -> &LazyHash<Library> {
+        self.library.as_ref().unwrap_or(&self.base.library)
+    }
+
+    fn book(&self) -> &LazyHash<FontBook> {
+        &self.base.book
+    }
+
+    fn main(&self) -> Source {
+        self.main.clone()
+    }
+
+    fn source(&self, id: FileId) -> FileResult<Source> {
+        if id == self.main.id() {
+            Ok(self.main.clone())
+        } else {
+            Err(FileError::NotFound(id.vpath().as_rootless_path().into()))
+        }
+    }
+
+    fn file(&self, id: FileId) -> FileResult<Bytes> {
+        Err(FileError::NotFound(id.vpath().as_rootless_path().into()))
+    }
+
+    fn font(&self, index: usize) -> Option<Font> {
+        self.base.fonts.get(index).cloned()
+    }
+
+    fn today(&self, _: Option<i64>) -> Option<Datetime> {
+        None
+    }
+}
+
+/// Shared foundation of all test worlds.
+struct TestBase {
+    library: LazyHash<Library>,
+    book: LazyHash<FontBook>,
+    fonts: Vec<Font>,
+}
+
+impl Default for TestBase {
+    fn default() -> Self {
+        let fonts: Vec<_> = typst_dev_assets::fonts()
+            .flat_map(|data| Font::iter(Bytes::from_static(data)))
+            .collect();
+
+        Self {
+            library: LazyHash::new(Library::default()),
+            book: LazyHash::new(FontBook::from_fonts(&fonts)),
+            fonts,
+        }
+    }
+}
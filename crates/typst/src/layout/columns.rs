@@ -14,10 +14,10 @@ use crate::utils::Numeric;
 /// Separates a region into multiple equally sized columns.
 ///
 /// The `column` function allows to separate the interior of any container into
-/// multiple columns. It will not equalize the height of the columns, instead,
-/// the columns will take up the height of their container or the remaining
-/// height on the page. The columns function can break across pages if
-/// necessary.
+/// multiple columns. If all content fits into the container or the remaining
+/// height on the page, the columns are [balanced]($columns.balance) so that
+/// they end at roughly the same height. Otherwise, the columns function breaks
+/// across pages.
 ///
 /// If you need to insert columns across your whole document, you can use the
 /// [`{page}` function's `columns` parameter]($page.columns) instead.
@@ -54,6 +54,44 @@ pub struct ColumnsElem {
     #[default(Ratio::new(0.04).into())]
     pub gutter: Rel<Length>,
 
+    /// Whether to balance the columns so that they end at roughly equal
+    /// heights.
+    ///
+    /// Balancing only applies if all content fits into the columns of the
+    /// first region. It works by searching for the smallest height at which
+    /// the content still fits, so it requires laying out the content multiple
+    /// times. Set this to `{false}` to fill the columns one after another
+    /// instead.
+    ///
+    /// The balanced height then acts as the height of each column:
+    /// - [Floats]($place.float) go to the top or bottom of their column, so a
+    ///   bottom float ends up where the balanced columns end rather than at
+    ///   the bottom of the container.
+    /// - [Footnotes]($footnote) are placed at the bottom of the column that
+    ///   references them, just like in unbalanced columns on a page. Their
+    ///   entries count towards the height of that column.
+    /// - Headings stay with the content that follows them. If that content
+    ///   moves to the next column, the heading moves with it.
+    ///
+    /// ```example
+    /// #block(height: 60pt, columns(2)[
+    ///   Balanced columns end at
+    ///   roughly the same height,
+    ///   even if there is space
+    ///   left below them.
+    /// ])
+    ///
+    /// #block(height: 60pt, columns(
+    ///   2,
+    ///   balance: false,
+    /// )[
+    ///   Unbalanced columns fill
+    ///   the first column first.
+    /// ])
+    /// ```
+    #[default(true)]
+    pub balance: bool,
+
     /// The content that should be layouted into the columns.
     #[required]
     pub body: Content,
@@ -107,7 +145,14 @@ fn layout_columns(
     };
 
     // Layout the children.
-    let mut frames = body.layout(engine, locator, styles, pod)?.into_iter();
+    let mut fragment = body.layout(engine, locator.relayout(), styles, pod)?;
+    if elem.balance(styles) && fragment.len() <= columns && pod.size.y.is_finite() {
+        if let Some(balanced) = balance(engine, &locator, styles, body, pod, columns)? {
+            fragment = balanced;
+        }
+    }
+
+    let mut frames = fragment.into_iter();
     let mut finished = vec![];
 
     let dir = TextElem::dir_in(styles);
@@ -143,6 +188,51 @@ fn layout_columns(
     Ok(Fragment::frames(finished))
 }
 
+/// Searches for the smallest column height at which the body still fits into
+/// the given number of columns and returns the resulting column frames.
+fn balance(
+    engine: &mut Engine,
+    locator: &Locator,
+    styles: StyleChain,
+    body: &Content,
+    pod: Regions,
+    columns: usize,
+) -> SourceResult<Option<Fragment>> {
+    // Stop searching once we are this close to the optimal height.
+    let tolerance = Abs::pt(1.0);
+
+    let mut lo = Abs::zero();
+    let mut hi = pod.size.y;
+    let mut best = None;
+
+    while hi - lo > tolerance {
+        let mid = (lo + hi) / 2.0;
+        let backlog = vec![mid; columns - 1];
+        let trial = Regions {
+            size: Size::new(pod.size.x, mid),
+            full: mid,
+            backlog: &backlog,
+            last: None,
+            expand: Axes::new(true, false),
+            root: pod.root,
+        };
+
+        // The content fits if it needs no more columns than available and
+        // does not overflow the last one.
+        let fragment = body.layout(engine, locator.relayout(), styles, trial)?;
+        if fragment.len() <= columns
+            && fragment.iter().all(|frame| mid.fits(frame.height()))
+        {
+            hi = mid;
+            best = Some(fragment);
+        } else {
+            lo = mid;
+        }
+    }
+
+    Ok(best)
+}
+
 /// Forces a column break.
 ///
 /// The function will behave like a [page break]($pagebreak) when used in a
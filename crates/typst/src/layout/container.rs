@@ -137,14 +137,15 @@ impl Packed<BoxElem> {
         let mut frame = match self.body(styles) {
             // If we have no body, just create an empty frame. If necessary,
             // its size will be adjusted below.
-            None => Frame::hard(Size::zero()),
+            None => Frame::new(Size::zero(), FrameKind::Container),
 
             // If we have a child, layout it into the body. Boxes are boundaries
-            // for gradient relativeness, so we set the `FrameKind` to `Hard`.
+            // for gradient relativeness, so we set the `FrameKind` to
+            // `Container`, which is hard.
             Some(body) => body
                 .layout(engine, locator, styles, pod.into_regions())?
                 .into_frame()
-                .with_kind(FrameKind::Hard),
+                .with_kind(FrameKind::Container),
         };
 
         // Enforce a correct frame size on the expanded axes. Do this before
@@ -345,6 +346,10 @@ pub struct BlockElem {
 
     /// Whether the block can be broken and continue on the next page.
     ///
+    /// An unbreakable block that does not fit into the remaining space moves
+    /// to the next page as a whole. If it is too large to fit onto any page,
+    /// it starts on an empty page and overflows it.
+    ///
     /// ```example
     /// #set page(height: 80pt)
     /// The following block will
@@ -517,11 +522,11 @@ impl Packed<BlockElem> {
             // be adjusted below.
             None => {
                 let mut frames = vec![];
-                frames.push(Frame::hard(Size::zero()));
+                frames.push(Frame::new(Size::zero(), FrameKind::Container));
                 if pod.expand.y {
                     let mut iter = pod;
                     while !iter.backlog.is_empty() {
-                        frames.push(Frame::hard(Size::zero()));
+                        frames.push(Frame::new(Size::zero(), FrameKind::Container));
                         iter.next();
                     }
                 }
@@ -608,7 +613,7 @@ impl Packed<BlockElem> {
         for (i, (frame, region)) in fragment.iter_mut().zip(pod.iter()).enumerate() {
             // Explicit blocks are boundaries for gradient relativeness.
             if is_explicit {
-                frame.set_kind(FrameKind::Hard);
+                frame.set_kind(FrameKind::Container);
             }
 
             // Enforce a correct frame size on the expanded axes. Do this before
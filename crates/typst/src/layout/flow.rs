@@ -402,6 +402,7 @@ impl<'a> FlowLayouter<'a> {
             )?
             .into_frame();
         frame.post_process(styles);
+        frame.set_placed(true);
         let item = FlowItem::Placed { frame, x_align, y_align, delta, float, clearance };
         self.layout_item(engine, item)
     }
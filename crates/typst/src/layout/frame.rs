@@ -6,10 +6,10 @@ use std::sync::Arc;
 
 use smallvec::SmallVec;
 
-use crate::foundations::{cast, dict, Dict, StyleChain, Value};
-use crate::introspection::Tag;
+use crate::foundations::{cast, dict, Content, Dict, StyleChain, Value};
+use crate::introspection::{Location, Tag};
 use crate::layout::{
-    Abs, Axes, Corners, FixedAlignment, HideElem, Length, Point, Rel, Sides, Size,
+    Abs, Axes, Corners, FixedAlignment, HideElem, Length, Point, Rect, Rel, Sides, Size,
     Transform,
 };
 use crate::model::{Destination, LinkElem};
@@ -34,6 +34,12 @@ pub struct Frame {
     ///
     /// Determines whether it is a boundary for gradient drawing.
     kind: FrameKind,
+    /// Whether the frame holds content that was placed out of the flow.
+    ///
+    /// This does not affect rendering, but lets consumers like [text
+    /// extraction](crate::model::Document::text) tell the content apart from
+    /// the flow. Placed frames are never inlined, so that the marker is kept.
+    placed: bool,
 }
 
 /// Constructor, accessors and setters.
@@ -49,6 +55,7 @@ impl Frame {
             baseline: None,
             items: Arc::new(LazyHash::new(vec![])),
             kind,
+            placed: false,
         }
     }
 
@@ -84,6 +91,16 @@ impl Frame {
         self.kind
     }
 
+    /// Marks the frame as holding content that was placed out of the flow.
+    pub fn set_placed(&mut self, placed: bool) {
+        self.placed = placed;
+    }
+
+    /// Whether the frame holds content that was placed out of the flow.
+    pub fn is_placed(&self) -> bool {
+        self.placed
+    }
+
     /// Whether the frame contains no items.
     pub fn is_empty(&self) -> bool {
         self.items.is_empty()
@@ -147,6 +164,79 @@ impl Frame {
     pub fn items(&self) -> std::slice::Iter<'_, (Point, FrameItem)> {
         self.items.iter()
     }
+
+    /// An iterator over the items of this frame and its nested groups
+    /// alongside their absolute positions.
+    ///
+    /// Groups are descended into instead of being yielded themselves, in the
+    /// order in which their items are drawn. Positions and transforms are
+    /// relative to the frame, transformed by `ts`.
+    pub fn flat_items(&self, ts: Transform) -> FlatItems<'_> {
+        FlatItems { stack: vec![(self.items(), ts, 0)] }
+    }
+
+    /// The bounding box of the items in the frame and its nested groups, if
+    /// there are any with an extent.
+    ///
+    /// In contrast to the frame's [size](Self::size), this tightly covers the
+    /// content, including items that overflow the frame, for example because
+    /// they are positioned at negative coordinates. To also cover the frame
+    /// itself, unite the result with the frame's size. Groups are transformed
+    /// with their transform and the bounds of a clipped group are cut to the
+    /// bounding box of its clip path. Text is measured from the font's
+    /// ascender to its descender and shapes are approximated by the bounding
+    /// box of their geometry, excluding the stroke.
+    pub fn bounds(&self) -> Option<Rect> {
+        self.items()
+            .filter_map(|&(pos, ref item)| {
+                let ts = Transform::translate(pos.x, pos.y);
+                let FrameItem::Group(group) = item else {
+                    return Some(item.bounds()?.transform(ts));
+                };
+
+                let mut bounds = group.frame.bounds()?;
+                if let Some(clip_path) = &group.clip_path {
+                    bounds = bounds.intersect(clip_path.bbox())?;
+                }
+                Some(bounds.transform(ts.pre_concat(group.transform)))
+            })
+            .reduce(Rect::union)
+    }
+
+    /// Finds the topmost locatable element at the given point.
+    ///
+    /// Elements don't have a size of their own, so an element is considered to
+    /// cover the frame its tag is placed in, as given by the frame's size.
+    /// Groups are searched by mapping the point into their coordinate system
+    /// with the inverse of their transform. When multiple elements cover the
+    /// point, the one that is drawn last wins, matching the z-order of the
+    /// rendered frame. Since an element's tag precedes its content, nested
+    /// elements thus win over the elements they are nested in.
+    pub fn find_at(&self, point: Point) -> Option<(Location, &Content)> {
+        let inside = Rect::from_pos_size(Point::zero(), self.size).contains(point);
+        let mut found = None;
+        for &(pos, ref item) in self.items() {
+            match item {
+                FrameItem::Tag(tag) if inside => {
+                    if let Some(loc) = tag.elem.location() {
+                        found = Some((loc, &tag.elem));
+                    }
+                }
+                FrameItem::Group(group) => {
+                    let ts =
+                        Transform::translate(pos.x, pos.y).pre_concat(group.transform);
+                    if let Some(hit) = ts
+                        .invert()
+                        .and_then(|inv| group.frame.find_at(point.transform(inv)))
+                    {
+                        found = Some(hit);
+                    }
+                }
+                _ => {}
+            }
+        }
+        found
+    }
 }
 
 /// Insert items and subframes.
@@ -220,8 +310,10 @@ impl Frame {
 
     /// Whether the given frame should be inlined.
     fn should_inline(&self, frame: &Frame) -> bool {
-        // We do not inline big frames and hard frames.
-        frame.kind().is_soft() && (self.items.is_empty() || frame.items.len() <= 5)
+        // We do not inline big frames, hard frames, and placed frames.
+        frame.kind().is_soft()
+            && !frame.is_placed()
+            && (self.items.is_empty() || frame.items.len() <= 5)
     }
 
     /// Inline a frame at the given layer.
@@ -295,6 +387,54 @@ impl Frame {
         offset
     }
 
+    /// Retains only the items for which the predicate returns `true`.
+    ///
+    /// Groups that are retained are filtered recursively, so this can be used
+    /// to strip, e.g., all tags from a frame and its groups. Groups that end
+    /// up empty are kept.
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&FrameItem) -> bool,
+    {
+        self.retain_impl(&mut f);
+    }
+
+    /// The recursive implementation of [`retain`](Self::retain).
+    fn retain_impl(&mut self, f: &mut dyn FnMut(&FrameItem) -> bool) {
+        Arc::make_mut(&mut self.items).retain_mut(|(_, item)| {
+            if !f(item) {
+                return false;
+            }
+            if let FrameItem::Group(group) = item {
+                group.frame.retain_impl(f);
+            }
+            true
+        });
+    }
+
+    /// Applies a function to the tags of the frame and its groups.
+    ///
+    /// This allows rewriting the introspectable elements before the frame is
+    /// introspected or exported, e.g. to strip internal markers or to rename
+    /// labels. Positions and transforms of all items stay untouched.
+    pub fn map_meta<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&mut Tag),
+    {
+        self.map_meta_impl(&mut f);
+    }
+
+    /// The recursive implementation of [`map_meta`](Self::map_meta).
+    fn map_meta_impl(&mut self, f: &mut dyn FnMut(&mut Tag)) {
+        for (_, item) in Arc::make_mut(&mut self.items).iter_mut() {
+            match item {
+                FrameItem::Tag(tag) => f(tag),
+                FrameItem::Group(group) => group.frame.map_meta_impl(f),
+                _ => {}
+            }
+        }
+    }
+
     /// Move the baseline and contents of the frame by an offset.
     pub fn translate(&mut self, offset: Point) {
         if !offset.is_zero() {
@@ -493,8 +633,14 @@ pub enum FrameKind {
     Soft,
     /// A container which uses its own size.
     ///
-    /// This is used for pages, blocks, and boxes.
+    /// This is used for pages and for layouts like columns and stacks.
     Hard,
+    /// An explicit box or block.
+    ///
+    /// Like a hard frame, it uses its own size. Additionally, it delimits its
+    /// contents from the surrounding flow, which determines the
+    /// [depth](crate::introspection::Introspector::depth) of elements.
+    Container,
 }
 
 impl FrameKind {
@@ -503,9 +649,14 @@ impl FrameKind {
         matches!(self, Self::Soft)
     }
 
-    /// Returns `true` if the frame is hard.
+    /// Returns `true` if the frame is hard, which includes containers.
     pub fn is_hard(self) -> bool {
-        matches!(self, Self::Hard)
+        matches!(self, Self::Hard | Self::Container)
+    }
+
+    /// Returns `true` if the frame is an explicit box or block.
+    pub fn is_container(self) -> bool {
+        matches!(self, Self::Container)
     }
 }
 
@@ -527,6 +678,30 @@ pub enum FrameItem {
     Tag(Tag),
 }
 
+impl FrameItem {
+    /// The bounding box of the item relative to its position, or `None` for
+    /// groups and tags.
+    ///
+    /// Text is measured from the font's ascender to its descender and shapes
+    /// are approximated by the bounding box of their geometry.
+    fn bounds(&self) -> Option<Rect> {
+        Some(match self {
+            Self::Text(text) => {
+                let metrics = text.font.metrics();
+                Rect::new(
+                    Point::with_y(-metrics.ascender.at(text.size)),
+                    Point::new(text.width(), -metrics.descender.at(text.size)),
+                )
+            }
+            Self::Shape(shape, _) => shape.geometry.bbox(),
+            Self::Image(_, size, _) | Self::Link(_, size) => {
+                Rect::from_pos_size(Point::zero(), *size)
+            }
+            Self::Group(_) | Self::Tag(_) => return None,
+        })
+    }
+}
+
 impl Debug for FrameItem {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         match self {
@@ -560,6 +735,13 @@ impl GroupItem {
             clip_path: None,
         }
     }
+
+    /// Whether the group is a boundary for the [depth](FlatItem::depth) of
+    /// the items nested in it, i.e. whether its frame is a
+    /// [container](FrameKind::Container) or it is transformed.
+    pub fn is_boundary(&self) -> bool {
+        self.frame.kind().is_container() || !self.transform.is_identity()
+    }
 }
 
 impl Debug for GroupItem {
@@ -569,6 +751,69 @@ impl Debug for GroupItem {
     }
 }
 
+/// An iterator over the items of a frame and its nested groups.
+///
+/// Created by [`Frame::flat_items`].
+pub struct FlatItems<'a> {
+    /// The items of the frames that are being iterated, with the transforms
+    /// and depths of their groups.
+    stack: Vec<(std::slice::Iter<'a, (Point, FrameItem)>, Transform, usize)>,
+}
+
+impl<'a> Iterator for FlatItems<'a> {
+    type Item = FlatItem<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (items, ts, depth) = self.stack.last_mut()?;
+            let (ts, depth) = (*ts, *depth);
+            let Some((pos, item)) = items.next() else {
+                self.stack.pop();
+                continue;
+            };
+
+            match item {
+                FrameItem::Group(group) => {
+                    let ts = ts
+                        .pre_concat(Transform::translate(pos.x, pos.y))
+                        .pre_concat(group.transform);
+                    self.stack.push((
+                        group.frame.items(),
+                        ts,
+                        depth + usize::from(group.is_boundary()),
+                    ));
+                }
+                _ => {
+                    return Some(FlatItem {
+                        pos: pos.transform(ts),
+                        item,
+                        ts: ts.pre_concat(Transform::translate(pos.x, pos.y)),
+                        depth,
+                    })
+                }
+            }
+        }
+    }
+}
+
+/// An item of a frame or one of its nested groups. Yielded by
+/// [`FlatItems`].
+#[derive(Debug, Copy, Clone)]
+pub struct FlatItem<'a> {
+    /// The item's absolute position.
+    pub pos: Point,
+    /// The item itself. Never a group.
+    pub item: &'a FrameItem,
+    /// The transform from the item's own coordinate system, in which it is at
+    /// the origin, to the absolute one. This combines the item's relative
+    /// position with the transforms of all groups it is nested in.
+    pub ts: Transform,
+    /// The number of boundary groups the item is nested in. A group is a
+    /// boundary if its frame is a [container](FrameKind::Container), i.e. an
+    /// explicit box or block, or if it is transformed.
+    pub depth: usize,
+}
+
 /// A physical position in a document.
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub struct Position {
@@ -599,3 +844,203 @@ impl From<Position> for Dict {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::introspection::testing::tag;
+    use crate::layout::Ratio;
+
+    #[test]
+    fn test_frame_bounds() {
+        let size = Size::new(Abs::pt(10.0), Abs::pt(10.0));
+        let link = || FrameItem::Link(Destination::Url("https://typst.app".into()), size);
+
+        let mut inner = Frame::hard(size);
+        inner.push(Point::new(Abs::pt(-5.0), Abs::pt(-2.0)), link());
+
+        let mut frame = Frame::soft(size);
+        frame.push(Point::with_x(Abs::pt(15.0)), link());
+        frame.push_frame(Point::with_y(Abs::pt(-10.0)), inner);
+
+        let bounds = frame.bounds().unwrap();
+        assert_eq!(bounds.min, Point::new(Abs::pt(-5.0), Abs::pt(-12.0)));
+        assert_eq!(bounds.max, Point::new(Abs::pt(25.0), Abs::pt(10.0)));
+        assert_eq!(bounds.size(), Size::new(Abs::pt(30.0), Abs::pt(22.0)));
+    }
+
+    #[test]
+    fn test_frame_bounds_shapes() {
+        let pt = |x, y| Point::new(Abs::pt(x), Abs::pt(y));
+        let shape = |geometry: Geometry| {
+            FrameItem::Shape(geometry.stroked(FixedStroke::default()), Span::detached())
+        };
+
+        let mut path = Path::new();
+        path.move_to(pt(2.0, 3.0));
+        path.line_to(pt(6.0, 4.0));
+        path.close_path();
+
+        let mut frame = Frame::soft(Size::zero());
+        frame.push(pt(10.0, 10.0), shape(Geometry::Line(pt(-4.0, 5.0))));
+        frame.push(pt(20.0, 0.0), shape(Geometry::Path(path)));
+
+        // The line extends to the left of its position and the path starts
+        // away from its origin.
+        let bounds = frame.bounds().unwrap();
+        assert_eq!(bounds.min, pt(6.0, 3.0));
+        assert_eq!(bounds.max, pt(26.0, 15.0));
+    }
+
+    #[test]
+    fn test_frame_bounds_clipped() {
+        let pt = |x, y| Point::new(Abs::pt(x), Abs::pt(y));
+        let size = Size::splat(Abs::pt(10.0));
+        let square = |side| {
+            FrameItem::Shape(
+                Geometry::Rect(Size::splat(Abs::pt(side))).filled(Color::BLACK.into()),
+                Span::detached(),
+            )
+        };
+
+        // The square overflows the group's frame to the bottom right, but is
+        // clipped to it.
+        let mut inner = Frame::soft(size);
+        inner.push(pt(5.0, 5.0), square(20.0));
+        let mut clipped = GroupItem::new(inner.clone());
+        clipped.clip_path = Some(Path::rect(size));
+        clipped.transform = Transform::translate(Abs::pt(2.0), Abs::zero());
+
+        let mut frame = Frame::soft(size);
+        frame.push(pt(10.0, 0.0), FrameItem::Group(clipped));
+        let bounds = frame.bounds().unwrap();
+        assert_eq!(bounds.min, pt(17.0, 5.0));
+        assert_eq!(bounds.max, pt(22.0, 10.0));
+
+        // Without the clip path, the overflow counts.
+        let mut frame = Frame::soft(size);
+        frame.push(pt(10.0, 0.0), FrameItem::Group(GroupItem::new(inner.clone())));
+        assert_eq!(frame.bounds().unwrap().max, pt(35.0, 25.0));
+
+        // A group whose content lies outside of its clip path has no extent.
+        let mut outside = GroupItem::new(inner);
+        outside.clip_path = Some(Path::rect(Size::splat(Abs::pt(4.0))));
+        let mut frame = Frame::soft(size);
+        frame.push(Point::zero(), FrameItem::Group(outside));
+        assert_eq!(frame.bounds(), None);
+    }
+
+    #[test]
+    fn test_frame_flat_items() {
+        let pt = |x, y| Point::new(Abs::pt(x), Abs::pt(y));
+        let square = |side| {
+            let size = Size::splat(Abs::pt(side));
+            FrameItem::Shape(
+                Geometry::Rect(size).filled(Color::BLACK.into()),
+                Span::detached(),
+            )
+        };
+
+        let mut inner = Frame::soft(Size::splat(Abs::pt(10.0)));
+        inner.push(pt(1.0, 2.0), square(2.0));
+        let mut group = GroupItem::new(inner);
+        group.transform = Transform::scale(Ratio::new(2.0), Ratio::new(2.0));
+
+        let mut frame = Frame::soft(Size::splat(Abs::pt(40.0)));
+        frame.push(pt(3.0, 0.0), square(1.0));
+        frame.push(pt(10.0, 10.0), FrameItem::Group(group));
+
+        let flat: Vec<_> = frame.flat_items(Transform::identity()).collect();
+        assert_eq!(flat.len(), 2);
+        assert_eq!((flat[0].pos, flat[0].depth), (pt(3.0, 0.0), 0));
+        assert_eq!((flat[1].pos, flat[1].depth), (pt(12.0, 14.0), 1));
+        assert_eq!(Point::zero().transform(flat[1].ts), flat[1].pos);
+
+        // The bounds cover the scaled square, but not the frame.
+        let bounds = frame.bounds().unwrap();
+        assert_eq!(bounds.min, pt(3.0, 0.0));
+        assert_eq!(bounds.max, pt(16.0, 18.0));
+        assert_eq!(Frame::soft(Size::splat(Abs::pt(5.0))).bounds(), None);
+    }
+
+    #[test]
+    fn test_frame_retain() {
+        let link = || {
+            FrameItem::Link(Destination::Url("https://typst.app".into()), Size::zero())
+        };
+        let mut inner = Frame::hard(Size::zero());
+        inner.push(Point::zero(), link());
+        inner.push(
+            Point::zero(),
+            FrameItem::Shape(
+                Geometry::Line(Point::zero()).stroked(FixedStroke::default()),
+                Span::detached(),
+            ),
+        );
+
+        let mut frame = Frame::soft(Size::zero());
+        frame.push(Point::zero(), link());
+        frame.push(Point::zero(), FrameItem::Group(GroupItem::new(inner)));
+
+        frame.retain(|item| !matches!(item, FrameItem::Link(..)));
+        assert_eq!(frame.items().len(), 1);
+        let FrameItem::Group(group) = &frame.items().next().unwrap().1 else {
+            panic!("expected group");
+        };
+        assert_eq!(group.frame.items().len(), 1);
+        assert!(matches!(group.frame.items().next().unwrap().1, FrameItem::Shape(..)));
+    }
+
+    #[test]
+    fn test_frame_hide() {
+        let size = Size::splat(Abs::pt(10.0));
+        let square = FrameItem::Shape(
+            Geometry::Rect(size).filled(Color::BLACK.into()),
+            Span::detached(),
+        );
+        let mut inner = Frame::hard(size);
+        inner.push(Point::zero(), square.clone());
+        let mut frame = Frame::soft(size);
+        frame.push(Point::zero(), tag(1));
+        frame.push(Point::zero(), square);
+        frame.push(Point::zero(), FrameItem::Group(GroupItem::new(inner)));
+
+        // Hiding keeps the size and the tag, but drops all ink, empty groups,
+        // and the links that are attached during post-processing.
+        let dests = SmallVec::from_buf([Destination::Url("https://typst.app".into())]);
+        frame.post_process_raw(dests, true);
+        assert_eq!(frame.size(), size);
+        assert_eq!(frame.items().len(), 1);
+        assert!(matches!(frame.items().next().unwrap().1, FrameItem::Tag(_)));
+        assert_eq!(
+            frame.find_at(Point::zero()).map(|(loc, _)| loc),
+            Some(Location::new(1))
+        );
+    }
+
+    #[test]
+    fn test_frame_find_at() {
+        let pt = |x, y| Point::new(Abs::pt(x), Abs::pt(y));
+        let square = Size::splat(Abs::pt(10.0));
+
+        // Two overlapping squares, of which the second one is moved by a
+        // transform instead of its position.
+        let mut first = Frame::hard(square);
+        first.push(Point::zero(), tag(2));
+        let mut second = Frame::hard(square);
+        second.push(Point::zero(), tag(3));
+        let mut group = GroupItem::new(second);
+        group.transform = Transform::translate(Abs::pt(5.0), Abs::pt(5.0));
+
+        let mut frame = Frame::soft(Size::splat(Abs::pt(20.0)));
+        frame.push(Point::zero(), tag(1));
+        frame.push_frame(Point::zero(), first);
+        frame.push(Point::zero(), FrameItem::Group(group));
+
+        let find = |p| frame.find_at(p).map(|(loc, _)| loc);
+        assert_eq!(find(pt(2.0, 2.0)), Some(Location::new(2)));
+        assert_eq!(find(pt(7.0, 7.0)), Some(Location::new(3)));
+        assert_eq!(find(pt(18.0, 2.0)), Some(Location::new(1)));
+        assert_eq!(find(pt(25.0, 25.0)), None);
+    }
+}
@@ -67,7 +67,8 @@ use crate::visualize::{Paint, Stroke};
 ///   the remaining space will be divided among the fractional tracks according
 ///   to their fractions. For example, if there are two fractional tracks, each
 ///   with a fraction of `{1fr}`, they will each take up half of the remaining
-///   space.
+///   space. If the `{auto}` tracks already claim all available space,
+///   fractional tracks end up with zero width.
 ///
 /// To specify a single track, the array can be omitted in favor of a single
 /// value. To specify multiple `{auto}` tracks, enter the number of tracks
@@ -119,6 +119,12 @@ pub(super) fn breakpoints<'a>(
                 break 'hyphenate;
             }
 
+            // Words with explicit soft hyphens are only broken at those, which
+            // the segmenter already reports as normal break opportunities.
+            if has_soft_hyphen(text, last, point) {
+                break 'hyphenate;
+            }
+
             let end = last + word.len();
             let mut offset = last;
 
@@ -226,6 +232,15 @@ fn hyphenate_at(p: &Preparation, offset: usize) -> bool {
         .unwrap_or(false)
 }
 
+/// Whether the word surrounding the segment `start..end` contains a soft
+/// hyphen.
+fn has_soft_hyphen(text: &str, start: usize, end: usize) -> bool {
+    let is_word = |c: char| c.is_alphabetic() || c == '\u{ad}';
+    let start = text[..start].trim_end_matches(is_word).len();
+    let end = text[end..].find(|c| !is_word(c)).map_or(text.len(), |i| end + i);
+    text[start..end].contains('\u{ad}')
+}
+
 /// The text language at the given offset.
 fn lang_at(p: &Preparation, offset: usize) -> Option<hypher::Lang> {
     let lang = p.lang.or_else(|| {
@@ -2,17 +2,19 @@ mod linebreak;
 mod shaping;
 
 use comemo::{Track, Tracked, TrackedMut};
+use ecow::EcoString;
+use indexmap::IndexMap;
 use unicode_bidi::{BidiInfo, Level as BidiLevel};
 use unicode_script::{Script, UnicodeScript};
 
 use self::linebreak::{breakpoints, Breakpoint};
 use self::shaping::{
-    cjk_punct_style, is_of_cj_script, shape, ShapedGlyph, ShapedText, BEGIN_PUNCT_PAT,
-    END_PUNCT_PAT,
+    cjk_punct_style, is_of_cj_script, shape, ShapedGlyph, ShapedText, Tracking,
+    BEGIN_PUNCT_PAT, END_PUNCT_PAT,
 };
-use crate::diag::{bail, SourceResult};
+use crate::diag::{bail, warning, SourceResult};
 use crate::engine::{Engine, Route, Sink, Traced};
-use crate::foundations::{Packed, Resolve, Smart, StyleChain};
+use crate::foundations::{Packed, Repr, Resolve, Smart, StyleChain};
 use crate::introspection::{Introspector, Locator, LocatorLink, Tag, TagElem};
 use crate::layout::{
     Abs, AlignElem, BoxElem, Dir, Em, FixedAlignment, Fr, Fragment, Frame, FrameItem,
@@ -22,8 +24,8 @@ use crate::model::{Linebreaks, ParElem};
 use crate::realize::StyleVec;
 use crate::syntax::Span;
 use crate::text::{
-    Costs, Lang, LinebreakElem, SmartQuoteElem, SmartQuoter, SmartQuotes, SpaceElem,
-    TextElem,
+    Costs, Font, Lang, LinebreakElem, SmartQuoteElem, SmartQuoter, SmartQuotes,
+    SpaceElem, TextElem,
 };
 use crate::utils::Numeric;
 use crate::World;
@@ -137,6 +139,8 @@ struct Preparation<'a> {
     justify: bool,
     /// The paragraph's hanging indent.
     hang: Abs,
+    /// The maximum spacing justification may add between letters.
+    tracking_limit: Abs,
     /// Whether to add spacing between CJK and Latin characters.
     cjk_latin_spacing: bool,
     /// Whether font fallback is enabled for this paragraph.
@@ -234,6 +238,11 @@ enum Item<'a> {
 }
 
 impl<'a> Item<'a> {
+    /// Whether the item takes up no space and has no visible content.
+    fn is_invisible(&self) -> bool {
+        matches!(self, Self::Tag(_) | Self::Skip(_))
+    }
+
     /// If this a text item, return it.
     fn text(&self) -> Option<&ShapedText<'a>> {
         match self {
@@ -405,6 +414,18 @@ impl<'a> Line<'a> {
         count
     }
 
+    /// How many gaps between letters there are in the line, into which
+    /// justification can insert tracking. Spaces are not letters, so the
+    /// gaps next to them don't count.
+    fn letter_gaps(&self) -> usize {
+        let letters: usize = self
+            .items()
+            .filter_map(Item::text)
+            .map(|s| s.glyphs.iter().filter(|g| !g.is_space()).count())
+            .sum();
+        letters.saturating_sub(1)
+    }
+
     /// How much can the line stretch
     fn stretchability(&self) -> Abs {
         self.items().filter_map(Item::text).map(|s| s.stretchability()).sum()
@@ -441,12 +462,13 @@ fn collect<'a>(
     let mut locator = locator.split();
 
     let first_line_indent = ParElem::first_line_indent_in(*styles);
-    if !first_line_indent.is_zero()
-        && consecutive
+    if !first_line_indent.amount.is_zero()
+        && (consecutive || first_line_indent.all)
         && AlignElem::alignment_in(*styles).resolve(*styles).x
             == TextElem::dir_in(*styles).start().into()
     {
-        collector.push_item(Item::Absolute(first_line_indent.resolve(*styles), false));
+        let amount = first_line_indent.amount.resolve(*styles);
+        collector.push_item(Item::Absolute(amount, false));
         collector.spans.push(1, Span::detached());
     }
 
@@ -665,6 +687,9 @@ fn prepare<'a>(
         cursor = end;
     }
 
+    warn_missing_glyphs(engine, &items);
+    warn_unsupported_smallcaps(engine, &items);
+
     let cjk_latin_spacing = TextElem::cjk_latin_spacing_in(styles).is_auto();
     if cjk_latin_spacing {
         add_cjk_latin_spacing(&mut items);
@@ -680,6 +705,7 @@ fn prepare<'a>(
         align: AlignElem::alignment_in(styles).resolve(styles).x,
         justify: ParElem::justify_in(styles),
         hang: ParElem::hanging_indent_in(styles),
+        tracking_limit: ParElem::tracking_limit_in(styles),
         cjk_latin_spacing,
         fallback: TextElem::fallback_in(styles),
         leading: ParElem::leading_in(styles),
@@ -688,6 +714,73 @@ fn prepare<'a>(
     })
 }
 
+/// Warn about characters that ended up as tofus because none of the available
+/// fonts has a glyph for them. Emits one warning per text span, listing each
+/// missing character once in logical order.
+fn warn_missing_glyphs(engine: &mut Engine, items: &[Item]) {
+    let mut missing: Vec<_> = items
+        .iter()
+        .filter_map(Item::text)
+        .flat_map(|shaped| shaped.glyphs.iter())
+        .filter(|glyph| {
+            glyph.glyph_id == 0 && !glyph.c.is_whitespace() && !glyph.c.is_control()
+        })
+        .collect();
+
+    if missing.is_empty() {
+        return;
+    }
+
+    // Glyphs of right-to-left runs are stored in visual order.
+    missing.sort_by_key(|glyph| glyph.range.start);
+
+    let mut grouped = IndexMap::<Span, EcoString>::new();
+    for glyph in missing {
+        let chars = grouped.entry(glyph.span.0).or_default();
+        if !chars.contains(glyph.c) {
+            chars.push(glyph.c);
+        }
+    }
+
+    for (span, chars) in grouped {
+        engine
+            .sink
+            .warn(warning!(span, "missing glyphs for {}", chars.repr()));
+    }
+}
+
+/// Warn about text in small capitals whose font has no `smcp` feature, since
+/// its lowercase letters then show up as regular ones.
+fn warn_unsupported_smallcaps(engine: &mut Engine, items: &[Item]) {
+    for shaped in items.iter().filter_map(Item::text) {
+        if !TextElem::smallcaps_in(shaped.styles) {
+            continue;
+        }
+
+        let Some(glyph) = shaped.glyphs.iter().find(|glyph| {
+            glyph.glyph_id != 0 && glyph.c.is_lowercase() && !has_smallcaps(&glyph.font)
+        }) else {
+            continue;
+        };
+
+        engine.sink.warn(warning!(
+            glyph.span.0,
+            "font family {} does not support small capitals",
+            glyph.font.info().family.as_str().repr();
+            hint: "lowercase letters are shown in their regular form"
+        ));
+    }
+}
+
+/// Whether the font substitutes small capitals via the `smcp` feature.
+fn has_smallcaps(font: &Font) -> bool {
+    font.ttf()
+        .tables()
+        .gsub
+        .and_then(|gsub| gsub.features.find(ttf_parser::Tag::from_bytes(b"smcp")))
+        .is_some()
+}
+
 /// Add some spacing between Han characters and western characters.
 /// See Requirements for Chinese Text Layout, Section 3.2.2 Mixed Text Composition in Horizontal
 /// Written Mode
@@ -809,6 +902,10 @@ fn is_compatible(a: Script, b: Script) -> bool {
 
 /// Find suitable linebreaks.
 fn linebreak<'a>(engine: &Engine, p: &'a Preparation<'a>, width: Abs) -> Vec<Line<'a>> {
+    // Paragraphs with more text than this (in bytes) always use simple line
+    // breaking because optimized line breaking gets too slow for them.
+    const MAX_OPTIMIZED_LEN: usize = 100_000;
+
     let linebreaks = p.linebreaks.unwrap_or_else(|| {
         if p.justify {
             Linebreaks::Optimized
@@ -818,8 +915,10 @@ fn linebreak<'a>(engine: &Engine, p: &'a Preparation<'a>, width: Abs) -> Vec<Lin
     });
 
     match linebreaks {
-        Linebreaks::Simple => linebreak_simple(engine, p, width),
-        Linebreaks::Optimized => linebreak_optimized(engine, p, width),
+        Linebreaks::Optimized if p.bidi.text.len() <= MAX_OPTIMIZED_LEN => {
+            linebreak_optimized(engine, p, width)
+        }
+        _ => linebreak_simple(engine, p, width),
     }
 }
 
@@ -1334,14 +1433,30 @@ fn commit(
         offset += p.hang;
     }
 
-    // Handle hanging punctuation to the left.
-    if let Some(Item::Text(text)) = reordered.first() {
+    // Handle hanging punctuation. Tags and skipped items are invisible, so
+    // we look past them to find the glyphs at the visual edges of the line.
+    let mut visible = reordered.iter().filter(|item| !item.is_invisible());
+    let first = visible.next();
+    let last = visible.next_back().or(first);
+    let single = first.is_some_and(|first| {
+        std::ptr::eq(first, last.unwrap())
+            && first.text().map_or(true, |t| t.glyphs.len() <= 1)
+    });
+
+    // Handle hanging punctuation to the left. In left-to-right text, this is
+    // the start of the line, so only opening punctuation hangs and only if
+    // the paragraph is justified.
+    if let Some(Item::Text(text)) = first {
         if let Some(glyph) = text.glyphs.first() {
-            if !text.dir.is_positive()
-                && TextElem::overhang_in(text.styles)
-                && (reordered.len() > 1 || text.glyphs.len() > 1)
-            {
-                let amount = overhang(glyph.c) * glyph.x_advance.at(text.size);
+            if TextElem::overhang_in(text.styles) && !single {
+                let factor = if !text.dir.is_positive() {
+                    overhang(glyph.c)
+                } else if p.justify {
+                    overhang_start(glyph.c)
+                } else {
+                    0.0
+                };
+                let amount = factor * glyph.x_advance.at(text.size);
                 offset -= amount;
                 remaining += amount;
             }
@@ -1349,13 +1464,17 @@ fn commit(
     }
 
     // Handle hanging punctuation to the right.
-    if let Some(Item::Text(text)) = reordered.last() {
+    if let Some(Item::Text(text)) = last {
         if let Some(glyph) = text.glyphs.last() {
-            if text.dir.is_positive()
-                && TextElem::overhang_in(text.styles)
-                && (reordered.len() > 1 || text.glyphs.len() > 1)
-            {
-                let amount = overhang(glyph.c) * glyph.x_advance.at(text.size);
+            if TextElem::overhang_in(text.styles) && !single {
+                let factor = if text.dir.is_positive() {
+                    overhang(glyph.c)
+                } else if p.justify {
+                    overhang_start(glyph.c)
+                } else {
+                    0.0
+                };
+                let amount = factor * glyph.x_advance.at(text.size);
                 remaining += amount;
             }
         }
@@ -1363,11 +1482,13 @@ fn commit(
 
     // Determine how much additional space is needed.
     // The justification_ratio is for the first step justification,
+    // extra_tracking is for the optional second step and
     // extra_justification is for the last step.
     // For more info on multi-step justification, see Procedures for Inter-
     // Character Space Expansion in W3C document Chinese Layout Requirements.
     let fr = line.fr();
     let mut justification_ratio = 0.0;
+    let mut extra_tracking = Abs::zero();
     let mut extra_justification = Abs::zero();
 
     let shrinkability = line.shrinkability();
@@ -1383,6 +1504,14 @@ fn commit(
             remaining = (remaining - stretch).max(Abs::zero());
         }
 
+        // Before stretching spaces beyond their limit, add a bounded amount
+        // of tracking between letters.
+        let gaps = line.letter_gaps();
+        if p.tracking_limit > Abs::zero() && gaps > 0 && remaining > Abs::zero() {
+            extra_tracking = (remaining / gaps as f64).min(p.tracking_limit);
+            remaining -= extra_tracking * gaps as f64;
+        }
+
         let justifiables = line.justifiables();
         if justifiables > 0 && remaining > Abs::zero() {
             // Underfull line, distribute the extra space.
@@ -1394,9 +1523,15 @@ fn commit(
     let mut top = Abs::zero();
     let mut bottom = Abs::zero();
 
+    // The visually last letter of the line gets no tracking.
+    let last_text = reordered.iter().rposition(|item| {
+        item.text()
+            .is_some_and(|shaped| shaped.glyphs.iter().any(|g| !g.is_space()))
+    });
+
     // Build the frames and determine the height and baseline.
     let mut frames = vec![];
-    for item in reordered {
+    for (i, item) in reordered.into_iter().enumerate() {
         let mut push = |offset: &mut Abs, frame: Frame| {
             let width = frame.width();
             top.set_max(frame.baseline());
@@ -1423,8 +1558,16 @@ fn commit(
                 }
             }
             Item::Text(shaped) => {
-                let mut frame =
-                    shaped.build(engine, justification_ratio, extra_justification);
+                let tracking = Tracking {
+                    amount: extra_tracking,
+                    trailing: Some(i) != last_text,
+                };
+                let mut frame = shaped.build(
+                    engine,
+                    justification_ratio,
+                    extra_justification,
+                    tracking,
+                );
                 frame.post_process(shaped.styles);
                 push(&mut offset, frame);
             }
@@ -1516,6 +1659,9 @@ fn overhang(c: char) -> f64 {
         '.' | ',' => 0.8,
         ':' | ';' => 0.3,
 
+        // Closing quotes.
+        '"' | '”' | '\'' | '’' | '»' | '›' => 0.5,
+
         // Arabic
         '\u{60C}' | '\u{6D4}' => 0.4,
 
@@ -1523,6 +1669,15 @@ fn overhang(c: char) -> f64 {
     }
 }
 
+/// How much a character should hang into the start margin.
+fn overhang_start(c: char) -> f64 {
+    match c {
+        // Opening quotes.
+        '"' | '“' | '„' | '\'' | '‘' | '‚' | '«' | '‹' => 0.5,
+        _ => 0.0,
+    }
+}
+
 /// Whether the hyphen should repeat at the start of the next line.
 fn should_repeat_hyphen(pred_line: &Line) -> bool {
     // If the predecessor line does not end with a Dash::HardHyphen, we shall
@@ -207,16 +207,28 @@ enum Side {
     Right,
 }
 
+/// Spacing that justification adds between the letters of a shaped text.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct Tracking {
+    /// The spacing added after each letter.
+    pub amount: Abs,
+    /// Whether the last letter also gets the spacing. This is `false` for the
+    /// text that ends its line.
+    pub trailing: bool,
+}
+
 impl<'a> ShapedText<'a> {
     /// Build the shaped text's frame.
     ///
     /// The `justification` defines how much extra advance width each
-    /// [justifiable glyph](ShapedGlyph::is_justifiable) will get.
+    /// [justifiable glyph](ShapedGlyph::is_justifiable) will get. The
+    /// `tracking` defines how much is added after each letter.
     pub fn build(
         &self,
         engine: &Engine,
         justification_ratio: f64,
         extra_justification: Abs,
+        tracking: Tracking,
     ) -> Frame {
         let (top, bottom) = self.measure(engine);
         let size = Size::new(self.width, top + bottom);
@@ -230,6 +242,7 @@ impl<'a> ShapedText<'a> {
         let fill = TextElem::fill_in(self.styles);
         let stroke = TextElem::stroke_in(self.styles);
         let span_offset = TextElem::span_offset_in(self.styles);
+        let last_letter = self.glyphs.iter().rfind(|g| !g.is_space());
 
         for ((font, y_offset), group) in
             self.glyphs.as_ref().group_by_key(|g| (g.font.clone(), g.y_offset))
@@ -262,6 +275,14 @@ impl<'a> ShapedText<'a> {
                         justification_right +=
                             Em::from_length(extra_justification, self.size)
                     }
+                    if !shaped.is_space()
+                        && (tracking.trailing
+                            || !last_letter
+                                .is_some_and(|last| std::ptr::eq(shaped, last)))
+                    {
+                        justification_right +=
+                            Em::from_length(tracking.amount, self.size);
+                    }
 
                     frame.size_mut().x += justification_left.at(self.size)
                         + justification_right.at(self.size);
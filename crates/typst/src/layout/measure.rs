@@ -43,6 +43,11 @@ use crate::syntax::Span;
 ///
 /// The measure function returns a dictionary with the entries `width` and
 /// `height`, both of type [`length`].
+///
+/// Note that measurement happens without knowledge of the container the
+/// content will finally end up in. Relative sizes like `{50%}` are thus
+/// resolved against the given `width` and `height` (or an infinite area)
+/// rather than against the final container.
 #[func(contextual)]
 pub fn measure(
     /// The engine.
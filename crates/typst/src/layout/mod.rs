@@ -26,6 +26,7 @@ mod page;
 mod place;
 mod point;
 mod ratio;
+mod rect;
 mod regions;
 mod rel;
 mod repeat;
@@ -58,6 +59,7 @@ pub use self::page::*;
 pub use self::place::*;
 pub use self::point::*;
 pub use self::ratio::*;
+pub use self::rect::*;
 pub use self::regions::*;
 pub use self::rel::*;
 pub use self::repeat::*;
@@ -35,6 +35,12 @@ use crate::visualize::Paint;
 /// Pages can be set to use `{auto}` as their width or height. In this case, the
 /// pages will grow to fit their content on the respective axis.
 ///
+/// A page set rule in the middle of the document starts a new page with the
+/// new properties. If the current page is still empty, no additional page is
+/// created and the properties apply to the current page instead. This way, you
+/// can, for instance, switch to a different paper size halfway through a
+/// document.
+///
 /// The [Guide for Page Setup]($guides/page-setup-guide) explains how to use
 /// this and related functions to set up a document with many examples.
 ///
@@ -161,7 +167,9 @@ pub struct PageElem {
     /// How many columns the page has.
     ///
     /// If you need to insert columns into a page or other container, you can
-    /// also use the [`columns` function]($columns).
+    /// also use the [`columns` function]($columns). Unlike with that function,
+    /// the columns of a page are not [balanced]($columns.balance), but filled
+    /// one after another.
     ///
     /// ```example:single
     /// #set page(columns: 2, height: 4.8cm)
@@ -326,6 +334,27 @@ pub struct PageElem {
     #[borrowed]
     pub foreground: Option<Content>,
 
+    /// Whether to show the header and footer on blank pages.
+    ///
+    /// Blank pages are inserted by [page breaks]($pagebreak.to) that need to
+    /// continue on an even or odd page. If this is `{false}`, such pages are
+    /// left without header and footer. Their fill, background, and foreground
+    /// are still shown.
+    ///
+    /// ```example
+    /// #set page(
+    ///   height: 40pt,
+    ///   header: [Chapter 1],
+    ///   blank-marginals: false,
+    /// )
+    ///
+    /// First.
+    /// #pagebreak(to: "odd")
+    /// Third.
+    /// ```
+    #[default(true)]
+    pub blank_marginals: bool,
+
     /// The contents of the page(s).
     ///
     /// Multiple pages will be created if the content does not fit on a single
@@ -387,6 +416,7 @@ impl Packed<PageElem> {
         if columns.get() > 1 {
             child = ColumnsElem::new(child)
                 .with_count(columns)
+                .with_balance(false)
                 .pack()
                 .spanned(self.span());
         }
@@ -438,11 +468,13 @@ impl PageLayout<'_> {
 
         // Align the child to the pagebreak's parity.
         // Check for page count after adding the pending frames
+        let mut blank = None;
         if self.extend_to.is_some_and(|p| {
             !p.matches(page_counter.physical().get() + self.frames.len())
         }) {
             // Insert empty page after the current pages.
             let size = self.area.map(Abs::is_finite).select(self.area, Size::zero());
+            blank = Some(self.frames.len());
             self.frames.push(Frame::hard(size));
         }
 
@@ -453,6 +485,7 @@ impl PageLayout<'_> {
         let footer_descent = self.page.footer_descent(styles);
         let numbering = self.page.numbering(styles);
         let number_align = self.page.number_align(styles);
+        let blank_marginals = self.page.blank_marginals(styles);
         let binding =
             self.page
                 .binding(styles)
@@ -501,7 +534,7 @@ impl PageLayout<'_> {
 
         // Post-process pages.
         let mut pages = Vec::with_capacity(self.frames.len());
-        for mut frame in self.frames {
+        for (i, mut frame) in self.frames.into_iter().enumerate() {
             // The padded width of the page's content without margins.
             let pw = frame.width();
 
@@ -521,8 +554,15 @@ impl PageLayout<'_> {
             let size = frame.size();
 
             // Realize overlays.
+            let is_blank = blank == Some(i);
             for marginal in [header, footer, background, foreground] {
                 let Some(content) = marginal.as_ref() else { continue };
+                if is_blank
+                    && !blank_marginals
+                    && (ptr::eq(marginal, header) || ptr::eq(marginal, footer))
+                {
+                    continue;
+                }
 
                 let (pos, area, align);
                 if ptr::eq(marginal, header) {
@@ -1066,3 +1106,35 @@ papers! {
     (PRESENTATION_16_9:    297.0, 167.0625, "presentation-16-9")
     (PRESENTATION_4_3:     280.0,    210.0, "presentation-4-3")
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::foundations::NativeElement;
+    use crate::introspection::testing::TestWorld;
+    use crate::introspection::MetadataElem;
+
+    #[test]
+    fn test_page_blank_marginals() {
+        // The pages on which a header is shown.
+        let headed = |blank_marginals: bool| -> Vec<usize> {
+            let world = TestWorld::new(&format!(
+                "#set page(header: metadata(none), blank-marginals: {blank_marginals})\n\
+                 A\n\
+                 #pagebreak(to: \"odd\")\n\
+                 B"
+            ));
+            let doc = crate::compile(&world).output.unwrap();
+            let introspector = &doc.introspector;
+            assert_eq!(doc.pages.len(), 3);
+            introspector
+                .query(&MetadataElem::elem().select())
+                .iter()
+                .map(|elem| introspector.page(elem.location().unwrap()).get())
+                .collect()
+        };
+
+        // The blank second page only has a header if requested.
+        assert_eq!(headed(true), [1, 2, 3]);
+        assert_eq!(headed(false), [1, 3]);
+    }
+}
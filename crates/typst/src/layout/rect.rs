@@ -0,0 +1,63 @@
+use crate::layout::{Point, Size, Transform};
+
+/// An axis-aligned rectangle in 2D.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Rect {
+    /// The top-left corner.
+    pub min: Point,
+    /// The bottom-right corner.
+    pub max: Point,
+}
+
+impl Rect {
+    /// Create a new rectangle from its top-left and bottom-right corners.
+    pub const fn new(min: Point, max: Point) -> Self {
+        Self { min, max }
+    }
+
+    /// Create a new rectangle from its top-left corner and its size.
+    pub fn from_pos_size(pos: Point, size: Size) -> Self {
+        Self { min: pos, max: pos + size.to_point() }
+    }
+
+    /// The size of the rectangle.
+    pub fn size(self) -> Size {
+        (self.max - self.min).to_size()
+    }
+
+    /// Whether the point lies within the rectangle, including its edges.
+    pub fn contains(self, point: Point) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x)
+            && (self.min.y..=self.max.y).contains(&point.y)
+    }
+
+    /// The smallest rectangle containing both this and another rectangle.
+    pub fn union(self, other: Self) -> Self {
+        Self::new(self.min.min(other.min), self.max.max(other.max))
+    }
+
+    /// The area covered by both this and another rectangle, if they overlap.
+    pub fn intersect(self, other: Self) -> Option<Self> {
+        let min = self.min.max(other.min);
+        let max = self.max.min(other.max);
+        (min.x <= max.x && min.y <= max.y).then_some(Self::new(min, max))
+    }
+
+    /// The smallest rectangle containing this rectangle after transforming it
+    /// with the given transformation.
+    pub fn transform(self, ts: Transform) -> Self {
+        let corners = [
+            self.min,
+            Point::new(self.max.x, self.min.y),
+            Point::new(self.min.x, self.max.y),
+            self.max,
+        ]
+        .map(|corner| corner.transform(ts));
+
+        let mut rect = Self::new(corners[0], corners[0]);
+        for corner in &corners[1..] {
+            rect = rect.union(Self::new(*corner, *corner));
+        }
+        rect
+    }
+}
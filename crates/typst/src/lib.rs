@@ -60,13 +60,13 @@ use std::collections::HashSet;
 use std::ops::{Deref, Range};
 
 use comemo::{Track, Tracked, Validate};
-use ecow::{EcoString, EcoVec};
+use ecow::{eco_format, EcoString, EcoVec};
 use typst_timing::{timed, TimingScope};
 
-use crate::diag::{warning, FileResult, SourceDiagnostic, SourceResult, Warned};
+use crate::diag::{bail, warning, FileResult, SourceDiagnostic, SourceResult, Warned};
 use crate::engine::{Engine, Route, Sink, Traced};
 use crate::foundations::{
-    Array, Bytes, Datetime, Dict, Module, Scope, StyleChain, Styles, Value,
+    Array, Bytes, Datetime, Dict, Module, Repr, Scope, StyleChain, Styles, Value,
 };
 use crate::introspection::Introspector;
 use crate::layout::{Alignment, Dir};
@@ -118,19 +118,25 @@ fn compile_inner(
     )?
     .content();
 
+    let max_iterations = library.max_iterations.max(1);
+    let max_queries = library.max_queries;
+
     let mut iter = 0;
     let mut document = Document::default();
+    document.introspector.set_query_budget(max_queries);
 
     // Relayout until all introspections stabilize.
-    // If that doesn't happen within five attempts, we give up.
+    // If that doesn't happen within `max_iterations` attempts, we give up.
     loop {
         // The name of the iterations for timing scopes.
         const ITER_NAMES: &[&str] =
             &["layout (1)", "layout (2)", "layout (3)", "layout (4)", "layout (5)"];
-        let _scope = TimingScope::new(ITER_NAMES[iter], None);
+        let _scope =
+            TimingScope::new(ITER_NAMES.get(iter).copied().unwrap_or("layout"), None);
 
-        // Clear delayed errors.
+        // Clear delayed errors and requests for another pass.
         sink.delayed();
+        sink.invalidated();
 
         let constraint = <Introspector as Validate>::Constraint::new();
         let mut engine = Engine {
@@ -141,20 +147,76 @@ fn compile_inner(
             route: Route::default(),
         };
 
-        // Layout!
-        document = content.layout_document(&mut engine, styles)?;
-        document.introspector.rebuild(&document.pages);
+        // Layout! We move the previous introspector into the new document so
+        // that its allocations and generation carry over. If the query budget
+        // was exhausted, queries yielded no elements, so errors of the layout
+        // are likely caused by that and not reported.
+        let result = content.layout_document(&mut engine, styles);
+        if document.introspector.query_budget_exceeded() {
+            bail!(
+                Span::detached(),
+                "layout performed too many distinct queries";
+                hint: "at most {max_queries} distinct queries are allowed per layout pass";
+                hint: "check if a selector is created anew in a loop"
+            );
+        }
+        let mut next = result?;
+        next.introspector = std::mem::take(&mut document.introspector);
+        // From the second pass on, keep the locations of the previous pass
+        // around, so that we can point out elements that were located
+        // differently.
+        let previous = (iter > 0).then(|| next.introspector.key_locations());
+        // Before the last attempt, keep the queries of the previous pass
+        // around, so that we can point out one that did not stabilize.
+        let queries =
+            (iter + 1 >= max_iterations).then(|| next.introspector.cached_queries());
+        next.introspector.rebuild(&next.pages);
+        document = next;
         iter += 1;
 
-        if timed!("check stabilized", document.introspector.validate(&constraint)) {
+        // Validation replays the recorded introspector calls against the
+        // rebuilt introspector and stops at the first call whose result
+        // differs, so an unstable layout doesn't pay for the remaining calls.
+        // The next iteration records all of its calls anew regardless. An
+        // explicit request for another pass overrides a stable result.
+        let invalidated = sink.invalidated();
+        if timed!("check stabilized", document.introspector.validate(&constraint))
+            && !invalidated
+        {
             break;
         }
 
-        if iter >= 5 {
+        // Check that elements which are the same as in the previous pass also
+        // received the same locations. If they didn't, the layout is
+        // nondeterministic somewhere, which keeps it from converging. The
+        // sink deduplicates the warning across passes.
+        if let Some(elem) = previous
+            .and_then(|previous| document.introspector.unstable_key(&previous))
+            .and_then(|key| document.introspector.first_keyed(key))
+        {
             sink.warn(warning!(
-                Span::detached(), "layout did not converge within 5 attempts";
-                hint: "check if any states or queries are updating themselves"
+                elem.span(),
+                "{} was located differently than in the previous layout pass",
+                elem.func().name();
+                hint: "this keeps the layout from converging";
+                hint: "this is likely a bug in Typst, please report it"
             ));
+        }
+
+        if iter >= max_iterations {
+            let mut warning = warning!(
+                Span::detached(), "layout did not converge within {max_iterations} attempts";
+                hint: "check if any states or queries are updating themselves"
+            );
+            if let Some(selector) =
+                queries.and_then(|queries| document.introspector.unstable_query(&queries))
+            {
+                warning.hint(eco_format!(
+                    "the results of the query for `{}` kept changing",
+                    selector.repr()
+                ));
+            }
+            sink.warn(warning);
             break;
         }
     }
@@ -165,6 +227,18 @@ fn compile_inner(
         return Err(delayed);
     }
 
+    // Point out labels that can't be referenced unambiguously. Elements from
+    // the same source location share their warning.
+    for elem in document.introspector.duplicately_labelled() {
+        if !elem.span().is_detached() {
+            sink.warn(warning!(
+                elem.span(),
+                "label `{}` is attached to multiple elements",
+                elem.label().unwrap().repr()
+            ));
+        }
+    }
+
     Ok(document)
 }
 
@@ -304,9 +378,28 @@ pub struct Library {
     /// The standard library as a value.
     /// Used to provide the `std` variable.
     pub std: Value,
+    /// The maximum number of layout passes before [`compile`] gives up on
+    /// convergence and emits a warning.
+    pub max_iterations: usize,
+    /// The maximum number of distinct queries (including nested subqueries
+    /// like the ones behind counters and `before`/`after` selectors) that a
+    /// single layout pass may perform before [`compile`] aborts with an error.
+    pub max_queries: usize,
 }
 
 impl Library {
+    /// The default for [`max_iterations`](Self::max_iterations).
+    pub const DEFAULT_MAX_ITERATIONS: usize = 5;
+
+    /// The default for [`max_queries`](Self::max_queries).
+    ///
+    /// The default is generous: Even large documents that query once per
+    /// element stay well below it. A document that exceeds it most likely
+    /// creates a fresh selector in a loop, so compilation aborts instead of
+    /// spending more and more time on the queries. To support documents
+    /// beyond it, raise the limit with [`LibraryBuilder::with_max_queries`].
+    pub const DEFAULT_MAX_QUERIES: usize = 100_000;
+
     /// Create a new builder for a library.
     pub fn builder() -> LibraryBuilder {
         LibraryBuilder::default()
@@ -326,6 +419,8 @@ impl Default for Library {
 #[derive(Debug, Clone, Default)]
 pub struct LibraryBuilder {
     inputs: Option<Dict>,
+    max_iterations: Option<usize>,
+    max_queries: Option<usize>,
 }
 
 impl LibraryBuilder {
@@ -335,13 +430,36 @@ impl LibraryBuilder {
         self
     }
 
+    /// Configure the maximum number of layout passes. Defaults to
+    /// [`Library::DEFAULT_MAX_ITERATIONS`].
+    pub fn with_max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = Some(max_iterations);
+        self
+    }
+
+    /// Configure the maximum number of distinct queries per layout pass.
+    /// Defaults to [`Library::DEFAULT_MAX_QUERIES`].
+    pub fn with_max_queries(mut self, max_queries: usize) -> Self {
+        self.max_queries = Some(max_queries);
+        self
+    }
+
     /// Consumes the builder and returns a `Library`.
     pub fn build(self) -> Library {
         let math = math::module();
         let inputs = self.inputs.unwrap_or_default();
         let global = global(math.clone(), inputs);
         let std = Value::Module(global.clone());
-        Library { global, math, styles: Styles::new(), std }
+        Library {
+            global,
+            math,
+            styles: Styles::new(),
+            std,
+            max_iterations: self
+                .max_iterations
+                .unwrap_or(Library::DEFAULT_MAX_ITERATIONS),
+            max_queries: self.max_queries.unwrap_or(Library::DEFAULT_MAX_QUERIES),
+        }
     }
 }
 
@@ -1,13 +1,17 @@
+use std::num::NonZeroUsize;
+
 use ecow::EcoString;
 
 use crate::diag::{bail, HintedStrResult, SourceResult};
 use crate::engine::Engine;
 use crate::foundations::{
-    cast, elem, Args, Array, Construct, Content, Datetime, Packed, Smart, StyleChain,
-    Value,
+    cast, elem, Args, Array, Construct, Content, Datetime, Element, Packed, Selector,
+    Smart, StyleChain, Value,
+};
+use crate::introspection::{Introspector, Location, Locator, ManualPageCounter};
+use crate::layout::{
+    Abs, Em, FlatItem, Frame, FrameItem, Page, PageElem, Point, Position, Size, Transform,
 };
-use crate::introspection::{Introspector, Locator, ManualPageCounter};
-use crate::layout::{Page, PageElem};
 use crate::realize::StyleVec;
 
 /// The root element of a document and its metadata.
@@ -157,13 +161,507 @@ pub struct Document {
     pub introspector: Introspector,
 }
 
+impl Document {
+    /// The page with the given number.
+    ///
+    /// Page numbers start at one, just like the page numbers of
+    /// [locations](crate::introspection::Location::page).
+    pub fn page(&self, number: NonZeroUsize) -> Option<&Page> {
+        self.pages.get(number.get() - 1)
+    }
+
+    /// The number of pages in the document.
+    pub fn page_count(&self) -> usize {
+        self.pages.len()
+    }
+
+    /// The top-left corners of the pages when they are stacked vertically
+    /// with `gap` between them, alongside their page numbers.
+    ///
+    /// This maps the page-relative [positions](Position) of the introspector
+    /// into a single coordinate system, as used by viewers that show all
+    /// pages in one continuous scroll. All pages are aligned at the left.
+    pub fn page_locations(&self, gap: Abs) -> Vec<(NonZeroUsize, Point)> {
+        let mut y = Abs::zero();
+        self.pages
+            .iter()
+            .enumerate()
+            .map(|(i, page)| {
+                let origin = Point::with_y(y);
+                y += page.frame.height() + gap;
+                (NonZeroUsize::new(1 + i).unwrap(), origin)
+            })
+            .collect()
+    }
+
+    /// Summarizes the elements of the given kinds as a flat table of contents.
+    ///
+    /// The elements are taken from the introspector in document order, so
+    /// only locatable elements are found. No specific element is assumed: The
+    /// text of an entry is the plain text of the element's `body` field (or of
+    /// the whole element if it has none) and its level is the element's
+    /// `level` field, if that is a positive integer.
+    pub fn to_meta_summary(&self, kinds: &[Element]) -> Vec<SummaryEntry> {
+        let selector = Selector::Or(kinds.iter().map(|kind| kind.select()).collect());
+        self.introspector
+            .query(&selector)
+            .iter()
+            .map(|elem| {
+                let location = elem.location().unwrap();
+                let text = match elem.get_by_name("body") {
+                    Ok(Value::Content(body)) => body.plain_text(),
+                    _ => elem.plain_text(),
+                };
+                let level = elem
+                    .get_by_name("level")
+                    .ok()
+                    .and_then(|level| level.cast::<NonZeroUsize>().ok());
+                SummaryEntry {
+                    location,
+                    element: elem.func(),
+                    text: text.trim().into(),
+                    level,
+                    position: self.introspector.position(location),
+                }
+            })
+            .collect()
+    }
+
+    /// Extracts the document's plain text in reading order.
+    ///
+    /// The text is reconstructed from the laid out text runs: Runs on the same
+    /// baseline form a line and lines are joined with spaces, except if a line
+    /// ends with a hyphen inserted by the line breaker, which is then dropped
+    /// again. Lines that are vertically further apart than `tolerance` start a
+    /// new paragraph. The distance is measured from the baseline of one line
+    /// to the top of the next line's em box. With the default paragraph
+    /// settings, lines of a paragraph are 0.35em apart and paragraphs are
+    /// 0.9em apart, so a tolerance in between, like `{0.6em}`, works well.
+    ///
+    /// Paragraphs are separated by blank lines. Each page starts a new
+    /// paragraph and content placed out of the flow (e.g. with `place`) is
+    /// appended after the main flow of its page.
+    pub fn text(&self, tolerance: Em) -> String {
+        let mut paragraphs = vec![];
+        for page in &self.pages {
+            let mut runs = PageRuns::default();
+            runs.collect(&page.frame, Transform::identity(), None);
+            for runs in std::iter::once(runs.flow).chain(runs.placed) {
+                paragraphs.extend(join_runs(&runs, tolerance));
+            }
+        }
+        paragraphs.join("\n\n")
+    }
+
+    /// Whether the pages of two documents are structurally equal.
+    ///
+    /// See [`diff`](Self::diff) for what is compared.
+    pub fn structural_eq(&self, other: &Document) -> bool {
+        self.diff(other).is_empty()
+    }
+
+    /// Compares the pages of two documents structurally.
+    ///
+    /// Pages are compared by their size and by the kind, position, and size of
+    /// their [flattened items](Frame::flat_items), so groups only matter
+    /// through the positions of their items. Lengths are compared
+    /// approximately. Introspection tags are ignored because the
+    /// locations they carry may differ between otherwise identical layouts.
+    pub fn diff(&self, other: &Document) -> Vec<PageDiff> {
+        let mut diffs = vec![];
+        for i in 0..self.pages.len().max(other.pages.len()) {
+            let page = NonZeroUsize::new(1 + i).unwrap();
+            let (Some(left), Some(right)) = (self.pages.get(i), other.pages.get(i))
+            else {
+                diffs.push(PageDiff::Missing { page });
+                continue;
+            };
+
+            let (left, right) = (&left.frame, &right.frame);
+            if !size_approx_eq(left.size(), right.size()) {
+                diffs.push(PageDiff::Size {
+                    page,
+                    left: left.size(),
+                    right: right.size(),
+                });
+            }
+
+            let essences = |frame: &Frame| -> Vec<_> {
+                frame.flat_items(Transform::identity()).filter_map(essence).collect()
+            };
+            let (left_items, right_items) = (essences(left), essences(right));
+            for index in 0..left_items.len().max(right_items.len()) {
+                let equal = match (left_items.get(index), right_items.get(index)) {
+                    (Some(a), Some(b)) => {
+                        a.0 == b.0
+                            && a.1.x.approx_eq(b.1.x)
+                            && a.1.y.approx_eq(b.1.y)
+                            && size_approx_eq(a.2, b.2)
+                    }
+                    _ => false,
+                };
+                if !equal {
+                    diffs.push(PageDiff::Item { page, index });
+                }
+            }
+        }
+        diffs
+    }
+}
+
+/// An entry in a document's [summary](Document::to_meta_summary).
+#[derive(Debug, Clone, PartialEq)]
+pub struct SummaryEntry {
+    /// The location of the summarized element.
+    pub location: Location,
+    /// The kind of the summarized element.
+    pub element: Element,
+    /// The element's plain text.
+    pub text: EcoString,
+    /// The element's level, if it has one.
+    pub level: Option<NonZeroUsize>,
+    /// Where the element is in the document.
+    pub position: Position,
+}
+
+/// A structural difference between the pages of two documents.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum PageDiff {
+    /// The page only exists in one of the documents.
+    Missing { page: NonZeroUsize },
+    /// The pages have different sizes.
+    Size { page: NonZeroUsize, left: Size, right: Size },
+    /// The item with the given index among the page's flattened items differs
+    /// in kind, position, or size, or only exists on one of the pages.
+    Item { page: NonZeroUsize, index: usize },
+}
+
+/// The structural essence of a flattened frame item: Its kind, absolute
+/// position, and size. Tags have none.
+fn essence(flat: FlatItem) -> Option<(&'static str, Point, Size)> {
+    let (kind, size) = match flat.item {
+        FrameItem::Text(text) => ("text", Size::new(text.width(), Abs::zero())),
+        FrameItem::Shape(shape, _) => ("shape", shape.geometry.bbox_size()),
+        FrameItem::Image(_, size, _) => ("image", *size),
+        FrameItem::Link(_, size) => ("link", *size),
+        FrameItem::Group(_) | FrameItem::Tag(_) => return None,
+    };
+    Some((kind, flat.pos, size))
+}
+
+/// A laid out piece of text, for [text extraction](Document::text).
+struct TextRun {
+    /// The text, without soft hyphens.
+    text: String,
+    /// The start of the run's baseline.
+    start: Point,
+    /// The end of the run's baseline.
+    end: Point,
+    /// The font size.
+    size: Abs,
+    /// Whether the run ends with a hyphen inserted by the line breaker. Such
+    /// a hyphen is not part of the run's text.
+    hyphenated: bool,
+}
+
+/// The text runs of a page, for [text extraction](Document::text).
+#[derive(Default)]
+struct PageRuns {
+    /// The runs of the main flow.
+    flow: Vec<TextRun>,
+    /// The runs of each placed frame, in the order in which the frames start.
+    placed: Vec<Vec<TextRun>>,
+}
+
+impl PageRuns {
+    /// Collect the text runs of a frame and its groups in document order.
+    ///
+    /// The runs of [placed](Frame::is_placed) frames are collected separately,
+    /// one list per frame. `index` is the list of the innermost placed frame
+    /// that `frame` lies in, if any.
+    fn collect(&mut self, frame: &Frame, ts: Transform, index: Option<usize>) {
+        for &(pos, ref item) in frame.items() {
+            match item {
+                FrameItem::Group(group) => {
+                    let ts = ts
+                        .pre_concat(Transform::translate(pos.x, pos.y))
+                        .pre_concat(group.transform);
+                    let index = if group.frame.is_placed() {
+                        self.placed.push(vec![]);
+                        Some(self.placed.len() - 1)
+                    } else {
+                        index
+                    };
+                    self.collect(&group.frame, ts, index);
+                }
+                FrameItem::Text(text) => {
+                    let runs = match index {
+                        Some(index) => &mut self.placed[index],
+                        None => &mut self.flow,
+                    };
+                    runs.push(TextRun {
+                        text: text.text.replace('\u{ad}', ""),
+                        start: pos.transform(ts),
+                        end: (pos + Point::with_x(text.width())).transform(ts),
+                        size: text.size,
+                        hyphenated: text
+                            .glyphs
+                            .last()
+                            .is_some_and(|glyph| glyph.range.is_empty()),
+                    });
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Join text runs into paragraphs. See [`Document::text`] for details.
+fn join_runs(runs: &[TextRun], tolerance: Em) -> Vec<String> {
+    let mut paragraphs = vec![];
+    let mut current = String::new();
+    let mut prev: Option<&TextRun> = None;
+
+    for run in runs {
+        if let Some(prev) = prev {
+            let size = prev.size.max(run.size);
+            let same_line = (run.start.y - prev.end.y).abs() < size / 2.0
+                && run.start.x >= prev.end.x - size / 10.0;
+            let separate = if same_line {
+                // Runs on the same line typically carry their spaces, but
+                // there may be visible gaps, e.g. due to `h` spacing.
+                run.start.x - prev.end.x > size / 5.0
+            } else if prev.hyphenated {
+                false
+            } else if run.start.y - run.size - prev.end.y > tolerance.at(prev.size) {
+                paragraphs.push(std::mem::take(&mut current));
+                false
+            } else {
+                true
+            };
+
+            if separate
+                && !current.ends_with(char::is_whitespace)
+                && !run.text.starts_with(char::is_whitespace)
+            {
+                current.push(' ');
+            }
+        }
+
+        current.push_str(&run.text);
+        prev = Some(run);
+    }
+
+    paragraphs.push(current);
+    paragraphs
+        .into_iter()
+        .map(|paragraph| paragraph.trim().to_string())
+        .filter(|paragraph| !paragraph.is_empty())
+        .collect()
+}
+
+/// Whether two sizes are approximately equal.
+fn size_approx_eq(a: Size, b: Size) -> bool {
+    a.x.approx_eq(b.x) && a.y.approx_eq(b.y)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::foundations::Bytes;
+    use crate::foundations::NativeElement;
+    use crate::introspection::testing::{page, tagged};
+    use crate::introspection::MetadataElem;
+    use crate::layout::GroupItem;
+    use crate::model::HeadingElem;
+    use crate::syntax::Span;
+    use crate::text::{Font, Glyph, Lang, TextElem, TextItem};
+    use crate::utils::NonZeroExt;
+    use crate::visualize::{Color, Geometry};
 
     #[test]
     fn test_document_is_send_and_sync() {
         fn ensure_send_and_sync<T: Send + Sync>() {}
         ensure_send_and_sync::<Document>();
     }
+
+    #[test]
+    fn test_document_page() {
+        let page = |number| Page {
+            frame: Frame::soft(Size::zero()),
+            numbering: None,
+            number,
+        };
+        let document = Document {
+            pages: vec![page(1), page(2)],
+            ..Default::default()
+        };
+        assert_eq!(document.page_count(), 2);
+        assert_eq!(document.page(NonZeroUsize::ONE).map(|page| page.number), Some(1));
+        assert_eq!(
+            document.page(NonZeroUsize::new(2).unwrap()).map(|page| page.number),
+            Some(2)
+        );
+        assert!(document.page(NonZeroUsize::new(3).unwrap()).is_none());
+    }
+
+    #[test]
+    fn test_document_page_locations() {
+        let sized = |width: f64, height: f64| {
+            page(Frame::soft(Size::new(Abs::pt(width), Abs::pt(height))))
+        };
+        let document = Document {
+            pages: vec![sized(100.0, 200.0), sized(300.0, 50.0), sized(100.0, 80.0)],
+            ..Default::default()
+        };
+        let locations: Vec<_> = document
+            .page_locations(Abs::pt(10.0))
+            .into_iter()
+            .map(|(number, point)| (number.get(), point.x.to_pt(), point.y.to_pt()))
+            .collect();
+        assert_eq!(locations, [(1, 0.0, 0.0), (2, 0.0, 210.0), (3, 0.0, 270.0)]);
+        assert!(Document::default().page_locations(Abs::pt(10.0)).is_empty());
+    }
+
+    #[test]
+    fn test_document_meta_summary() {
+        let heading = |title: &str, level: usize| {
+            HeadingElem::new(TextElem::packed(title))
+                .with_level(Smart::Custom(NonZeroUsize::new(level).unwrap()))
+                .pack()
+        };
+
+        let mut first = Frame::soft(Size::zero());
+        first.push(Point::zero(), tagged(heading("Introduction", 1), 1));
+        first.push(Point::with_y(Abs::pt(10.0)), tagged(heading(" Motivation ", 2), 2));
+        first.push(Point::zero(), tagged(MetadataElem::new(Value::None).pack(), 3));
+        let mut second = Frame::soft(Size::zero());
+        second.push(Point::with_y(Abs::pt(5.0)), tagged(heading("Results", 1), 4));
+
+        let mut document = Document {
+            pages: vec![page(first), page(second)],
+            ..Default::default()
+        };
+        document.introspector.rebuild(&document.pages);
+
+        let summary = document.to_meta_summary(&[HeadingElem::elem()]);
+        let entries: Vec<_> = summary
+            .iter()
+            .map(|entry| {
+                (
+                    entry.location.hash(),
+                    entry.text.as_str(),
+                    entry.level.map(NonZeroUsize::get),
+                    entry.position.page.get(),
+                    entry.position.point.y,
+                )
+            })
+            .collect();
+        assert_eq!(
+            entries,
+            [
+                (1, "Introduction", Some(1), 1, Abs::zero()),
+                (2, "Motivation", Some(2), 1, Abs::pt(10.0)),
+                (4, "Results", Some(1), 2, Abs::pt(5.0)),
+            ]
+        );
+
+        // Elements without a body or level are summarized, too.
+        let summary = document.to_meta_summary(&[MetadataElem::elem()]);
+        assert_eq!(summary.len(), 1);
+        assert_eq!(summary[0].level, None);
+    }
+
+    #[test]
+    fn test_document_diff() {
+        let document = |shift: f64| {
+            let square =
+                Geometry::Rect(Size::splat(Abs::pt(5.0))).filled(Color::BLACK.into());
+            let mut inner = Frame::soft(Size::splat(Abs::pt(10.0)));
+            inner.push(
+                Point::with_x(Abs::pt(shift)),
+                FrameItem::Shape(square, Span::detached()),
+            );
+            let mut frame = Frame::soft(Size::splat(Abs::pt(20.0)));
+            frame.push(
+                Point::with_y(Abs::pt(5.0)),
+                FrameItem::Group(GroupItem::new(inner)),
+            );
+            Document { pages: vec![page(frame)], ..Default::default() }
+        };
+
+        assert!(document(0.0).structural_eq(&document(0.0)));
+        assert_eq!(
+            document(0.0).diff(&document(1.0)),
+            [PageDiff::Item { page: NonZeroUsize::ONE, index: 0 }]
+        );
+        assert_eq!(
+            document(0.0).diff(&Document::default()),
+            [PageDiff::Missing { page: NonZeroUsize::ONE }]
+        );
+    }
+
+    #[test]
+    fn test_document_text() {
+        let data = typst_dev_assets::fonts().next().unwrap();
+        let font = Font::new(Bytes::from_static(data), 0).unwrap();
+        let run = |text: &str, hyphenated: bool| {
+            let mut glyphs: Vec<_> = (0..text.len() as u16)
+                .map(|i| Glyph {
+                    id: 0,
+                    x_advance: Em::new(0.5),
+                    x_offset: Em::zero(),
+                    range: i..i + 1,
+                    span: (Span::detached(), 0),
+                })
+                .collect();
+            if hyphenated {
+                let end = text.len() as u16;
+                glyphs.push(Glyph { range: end..end, ..glyphs[0].clone() });
+            }
+            FrameItem::Text(TextItem {
+                font: font.clone(),
+                size: Abs::pt(10.0),
+                fill: Color::BLACK.into(),
+                stroke: None,
+                lang: Lang::ENGLISH,
+                region: None,
+                text: text.into(),
+                glyphs,
+            })
+        };
+        let at = |x: f64, y: f64| Point::new(Abs::pt(x), Abs::pt(y));
+
+        // Placed content comes first in the frame, but is read last.
+        let mut note = Frame::soft(Size::zero());
+        note.push(at(0.0, 5.0), run("Note", false));
+        note.set_placed(true);
+        let mut first = Frame::soft(Size::zero());
+        first.push_frame(Point::zero(), note);
+
+        // The lines of the first paragraph are 0.35em apart and the second
+        // paragraph is 0.9em below.
+        first.push(at(0.0, 10.0), run("Hello ", false));
+        first.push(at(30.0, 10.0), run("wor", true));
+        first.push(at(0.0, 23.5), run("ld and more", false));
+        first.push(at(0.0, 42.5), run("Second\u{ad}para", false));
+
+        let mut second = Frame::soft(Size::zero());
+        second.push(at(0.0, 10.0), run("Next", false));
+        second.push(at(30.0, 10.0), run("page", false));
+
+        let document = Document {
+            pages: vec![page(first), page(second)],
+            ..Default::default()
+        };
+        assert_eq!(
+            document.text(Em::new(0.6)),
+            "Hello world and more\n\nSecondpara\n\nNote\n\nNext page"
+        );
+
+        // With a large tolerance, both paragraphs are merged.
+        assert!(document
+            .text(Em::new(1.0))
+            .starts_with("Hello world and more Secondpara"));
+    }
 }
@@ -1,7 +1,7 @@
 use std::num::NonZeroUsize;
 use std::str::FromStr;
 
-use crate::diag::{bail, At, SourceResult, StrResult};
+use crate::diag::{bail, At, HintedStrResult, SourceResult};
 use crate::engine::Engine;
 use crate::foundations::{
     cast, elem, scope, Content, Label, NativeElement, Packed, Show, ShowSet, Smart,
@@ -109,7 +109,7 @@ impl FootnoteElem {
 
 impl Packed<FootnoteElem> {
     /// Returns the location of the definition of this footnote.
-    pub fn declaration_location(&self, engine: &Engine) -> StrResult<Location> {
+    pub fn declaration_location(&self, engine: &Engine) -> HintedStrResult<Location> {
         match self.body() {
             FootnoteBody::Reference(label) => {
                 let element = engine.introspector.query_label(*label)?;
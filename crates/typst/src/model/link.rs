@@ -1,12 +1,12 @@
 use ecow::{eco_format, EcoString};
 use smallvec::SmallVec;
 
-use crate::diag::{At, SourceResult};
+use crate::diag::SourceResult;
 use crate::engine::Engine;
 use crate::foundations::{
     cast, elem, Content, Label, Packed, Repr, Show, Smart, StyleChain,
 };
-use crate::introspection::Location;
+use crate::introspection::{Locatable, Location};
 use crate::layout::Position;
 use crate::text::{Hyphenate, TextElem};
 
@@ -27,10 +27,23 @@ use crate::text::{Hyphenate, TextElem};
 /// ]
 /// ```
 ///
+/// # Querying
+/// Links can be [queried]($query) like other elements, for example to list all
+/// external URLs of a document. A link that breaks across lines is still found
+/// once, at the position where it starts.
+///
+/// ```example
+/// #link("https://typst.app")[Typst] \
+/// #link("https://example.com") \
+/// #context query(link)
+///   .map(it => it.dest)
+///   .join(", ")
+/// ```
+///
 /// # Syntax
 /// This function also has dedicated syntax: Text that starts with `http://` or
 /// `https://` is automatically turned into a link.
-#[elem(Show)]
+#[elem(Locatable, Show)]
 pub struct LinkElem {
     /// The destination the link points to.
     ///
@@ -102,9 +115,8 @@ impl Show for Packed<LinkElem> {
         let linked = match self.dest() {
             LinkTarget::Dest(dest) => body.linked(dest.clone()),
             LinkTarget::Label(label) => {
-                let elem = engine.introspector.query_label(*label).at(self.span())?;
-                let dest = Destination::Location(elem.location().unwrap());
-                body.clone().linked(dest)
+                let (loc, _) = engine.resolve_label(*label, self.span())?;
+                body.clone().linked(Destination::Location(loc))
             }
         };
 
@@ -263,7 +263,7 @@ impl ShowSet for Packed<OutlineElem> {
         let mut out = Styles::new();
         out.set(HeadingElem::set_outlined(false));
         out.set(HeadingElem::set_numbering(None));
-        out.set(ParElem::set_first_line_indent(Em::new(0.0).into()));
+        out.set(ParElem::set_first_line_indent(Length::from(Em::new(0.0)).into()));
         out
     }
 }
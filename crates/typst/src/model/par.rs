@@ -3,8 +3,8 @@ use std::fmt::{self, Debug, Formatter};
 use crate::diag::SourceResult;
 use crate::engine::Engine;
 use crate::foundations::{
-    elem, Args, Cast, Construct, Content, NativeElement, Packed, Set, Smart, StyleChain,
-    Unlabellable,
+    cast, dict, elem, Args, Cast, Construct, Content, Dict, NativeElement, Packed, Set,
+    Smart, StyleChain, Unlabellable, Value,
 };
 use crate::introspection::Locator;
 use crate::layout::{Em, Fragment, Length, Size};
@@ -90,6 +90,9 @@ pub struct ParElem {
     /// breaks for ragged paragraphs may also be worthwhile to improve the
     /// appearance of the text.
     ///
+    /// Extremely long paragraphs are always broken with the simple algorithm
+    /// to keep the layout time in check.
+    ///
     /// ```example
     /// #set page(width: 207pt)
     /// #set par(linebreaks: "simple")
@@ -109,22 +112,52 @@ pub struct ParElem {
 
     /// The indent the first line of a paragraph should have.
     ///
-    /// Only the first line of a consecutive paragraph will be indented (not
-    /// the first one in a block or on the page).
+    /// By default, only the first line of a consecutive paragraph will be
+    /// indented (not the first one in a block or on the page, or one following
+    /// a heading, figure, or other block-level element). To indent all
+    /// paragraphs instead, pass a dictionary with the `amount` of indent and
+    /// `{all: true}`. A plain length is short for a dictionary with just the
+    /// `amount`.
     ///
     /// By typographic convention, paragraph breaks are indicated either by some
     /// space between paragraphs or by indented first lines. Consider reducing
     /// the [paragraph spacing]($block.spacing) to the [`leading`]($par.leading)
     /// when using this property (e.g. using
     /// `[#show par: set block(spacing: 0.65em)]`).
+    ///
+    /// ```example
+    /// #set par(first-line-indent: (amount: 1em, all: true))
+    /// = Heading
+    /// This paragraph is indented even
+    /// though it follows a heading.
+    /// ```
     #[ghost]
-    pub first_line_indent: Length,
+    pub first_line_indent: FirstLineIndent,
 
     /// The indent all but the first line of a paragraph should have.
     #[ghost]
     #[resolve]
     pub hanging_indent: Length,
 
+    /// The maximum amount of spacing that justification may add between
+    /// letters.
+    ///
+    /// Justification first stretches the spaces of a line up to their natural
+    /// limit. If that is not enough to fill the line, up to this amount of
+    /// spacing is added between each pair of letters before the spaces are
+    /// stretched any further. This avoids wide gaps between words in narrow
+    /// columns. By default, the spacing between letters is never adjusted.
+    ///
+    /// ```example
+    /// #set page(width: 120pt)
+    /// #set par(justify: true, tracking-limit: 0.4pt)
+    /// Extraordinarily long words
+    /// in narrow columns are hard.
+    /// ```
+    #[ghost]
+    #[resolve]
+    pub tracking_limit: Length,
+
     /// Indicates wheter an overflowing line should be shrunk.
     ///
     /// This property is set to `false` on raw blocks, because shrinking a line
@@ -203,6 +236,42 @@ pub enum Linebreaks {
     Optimized,
 }
 
+/// Configuration for the indent of the first line of paragraphs.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Hash)]
+pub struct FirstLineIndent {
+    /// The indent of the first line.
+    pub amount: Length,
+    /// Whether to indent all paragraphs instead of just consecutive ones.
+    pub all: bool,
+}
+
+impl From<Length> for FirstLineIndent {
+    fn from(amount: Length) -> Self {
+        Self { amount, all: false }
+    }
+}
+
+cast! {
+    FirstLineIndent,
+    self => Value::Dict(self.into()),
+    amount: Length => amount.into(),
+    mut dict: Dict => {
+        let amount = dict.take("amount")?.cast()?;
+        let all = dict.take("all").ok().map(Value::cast).transpose()?.unwrap_or(false);
+        dict.finish(&["amount", "all"])?;
+        Self { amount, all }
+    },
+}
+
+impl From<FirstLineIndent> for Dict {
+    fn from(indent: FirstLineIndent) -> Self {
+        dict! {
+            "amount" => indent.amount,
+            "all" => indent.all,
+        }
+    }
+}
+
 /// A paragraph break.
 ///
 /// This starts a new paragraph. Especially useful when used within code like
@@ -159,6 +159,21 @@ impl Show for Packed<TermsElem> {
 }
 
 /// A term list item.
+///
+/// Show rules on term list items can restyle individual pairs of a term list.
+/// The styles of an item apply to its term and description, but the item is
+/// still laid out as part of the list.
+///
+/// ```example
+/// #show terms.item: it => if it.term == [Joker] {
+///   text(red, it)
+/// } else {
+///   it
+/// }
+///
+/// / King: The highest card.
+/// / Joker: The wildcard.
+/// ```
 #[elem(name = "item", title = "Term List Item")]
 pub struct TermItem {
     /// The term described by the list item.
@@ -196,9 +196,9 @@ fn prepare(
     // when it stems from a query.
     let mut key = None;
     if target.location().is_some() {
-        key = Some(crate::utils::hash128(&target));
+        key = Some(location_key(target));
     } else if target.can::<dyn Locatable>() || target.label().is_some() {
-        let hash = crate::utils::hash128(&target);
+        let hash = location_key(target);
         let location = locator.next_location(engine.introspector, hash);
         target.set_location(location);
         key = Some(hash);
@@ -314,3 +314,33 @@ fn show_regex(
     // All these problems don't exist for text, so it's fine here.
     Ok(Content::sequence(result).styled(Style::Revocation(index)))
 }
+
+/// The key from which an element's location is derived.
+///
+/// The hash of the content does not cover its span. We additionally hash the
+/// file the element originates from, so that identical elements from different
+/// files never share a key.
+fn location_key(target: &Content) -> u128 {
+    crate::utils::hash128(&(target, target.span().id()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::foundations::{NativeElement, Value};
+    use crate::introspection::MetadataElem;
+    use crate::syntax::{FileId, Span, VirtualPath};
+
+    #[test]
+    fn test_location_key_distinguishes_files() {
+        let elem = |path: &str| {
+            let id = FileId::new(None, VirtualPath::new(path));
+            MetadataElem::new(Value::Int(1))
+                .pack()
+                .spanned(Span::new(id, 10).unwrap())
+        };
+
+        assert_eq!(location_key(&elem("a.typ")), location_key(&elem("a.typ")));
+        assert_ne!(location_key(&elem("a.typ")), location_key(&elem("b.typ")));
+    }
+}
@@ -6,8 +6,8 @@ use std::sync::Arc;
 use ecow::{eco_format, EcoString};
 use serde::{Serialize, Serializer};
 
-use crate::diag::{bail, SourceResult, StrResult};
-use crate::foundations::{cast, func, scope, ty, Array, Func};
+use crate::diag::{bail, HintedStrResult, HintedString, SourceResult, StrResult};
+use crate::foundations::{cast, func, repr, scope, ty, Array, Func};
 use crate::syntax::{Span, Spanned};
 
 #[doc(inline)]
@@ -107,13 +107,18 @@ impl Symbol {
     }
 
     /// Apply a modifier to the symbol.
-    pub fn modified(mut self, modifier: &str) -> StrResult<Self> {
+    ///
+    /// If the modifier is unknown and there are only a few modifiers that
+    /// could be applied instead, the error hints at them.
+    pub fn modified(mut self, modifier: &str) -> HintedStrResult<Self> {
         if let Repr::Const(list) = self.0 {
             self.0 = Repr::Multi(Arc::new((List::Static(list), EcoString::new())));
         }
 
+        let mut error = HintedString::new("unknown symbol modifier".into());
         if let Repr::Multi(arc) = &mut self.0 {
             let (list, modifiers) = Arc::make_mut(arc);
+            let applied = modifiers.len();
             if !modifiers.is_empty() {
                 modifiers.push('.');
             }
@@ -121,9 +126,19 @@ impl Symbol {
             if find(list.variants(), modifiers).is_some() {
                 return Ok(self);
             }
+
+            let applicable = applicable(list.variants(), &modifiers[..applied]);
+            if !applicable.is_empty() && applicable.len() <= 10 {
+                let names: Vec<_> =
+                    applicable.iter().map(|name| eco_format!("`{name}`")).collect();
+                error.hint(eco_format!(
+                    "valid modifiers are {}",
+                    repr::separated_list(&names, "and")
+                ));
+            }
         }
 
-        bail!("unknown symbol modifier")
+        Err(error)
     }
 
     /// The characters that are covered by this symbol.
@@ -310,6 +325,20 @@ impl<'a> Iterator for Variants<'a> {
     }
 }
 
+/// The modifiers that can be applied in addition to the given ones.
+fn applicable<'a>(
+    variants: impl Iterator<Item = (&'a str, SymChar)>,
+    modifiers: &str,
+) -> BTreeSet<&'a str> {
+    let mut set = BTreeSet::new();
+    for (name, _) in variants {
+        if parts(modifiers).all(|m| contained(name, m)) {
+            set.extend(parts(name).filter(|part| !contained(modifiers, part)));
+        }
+    }
+    set
+}
+
 /// Find the best symbol from the list.
 fn find<'a>(
     variants: impl Iterator<Item = (&'a str, SymChar)>,
@@ -151,11 +151,10 @@ pub struct TextElem {
     /// contains no match. This lets Typst search through all available fonts
     /// for the most similar one that has the necessary glyphs.
     ///
-    /// _Note:_ Currently, there are no warnings when fallback is disabled and
-    /// no glyphs are found. Instead, your text shows up in the form of "tofus":
-    /// Small boxes that indicate the lack of an appropriate glyph. In the
-    /// future, you will be able to instruct Typst to issue warnings so you know
-    /// something is up.
+    /// When no font has a glyph for a character, your text shows up in the
+    /// form of "tofus": Small boxes that indicate the lack of an appropriate
+    /// glyph. Typst then issues a warning listing the missing characters so
+    /// you know something is up.
     ///
     /// ```example
     /// #set text(font: "Inria Serif")
@@ -326,6 +325,10 @@ pub struct TextElem {
     /// Whether certain glyphs can hang over into the margin in justified text.
     /// This can make justification visually more pleasing.
     ///
+    /// Punctuation like periods, commas, hyphens, and closing quotes hangs
+    /// over the end of a line. In justified paragraphs, opening quotes also
+    /// hang over the start of a line.
+    ///
     /// ```example
     /// #set par(justify: true)
     /// This justified text has a hyphen in
@@ -466,7 +469,10 @@ pub struct TextElem {
     /// will be hyphenated if and only if justification is enabled.
     ///
     /// Setting the [text language]($text.lang) ensures that the correct
-    /// hyphenation patterns are used.
+    /// hyphenation patterns are used. The language also determines how many
+    /// characters must at least remain before and after a hyphen. If a word
+    /// contains soft hyphens (`{sym.hyph.soft}`), it is only hyphenated at
+    /// those.
     ///
     /// ```example
     /// #set page(width: 200pt)
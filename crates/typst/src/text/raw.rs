@@ -10,7 +10,7 @@ use syntect::parsing::{SyntaxDefinition, SyntaxSet, SyntaxSetBuilder};
 use unicode_segmentation::UnicodeSegmentation;
 
 use super::Lang;
-use crate::diag::{At, FileError, HintedStrResult, SourceResult, StrResult};
+use crate::diag::{warning, At, FileError, HintedStrResult, SourceResult, StrResult};
 use crate::engine::Engine;
 use crate::foundations::{
     cast, elem, scope, Args, Array, Bytes, Content, Fold, NativeElement, Packed,
@@ -294,7 +294,20 @@ impl RawElem {
 }
 
 impl Synthesize for Packed<RawElem> {
-    fn synthesize(&mut self, _: &mut Engine, styles: StyleChain) -> SourceResult<()> {
+    fn synthesize(
+        &mut self,
+        engine: &mut Engine,
+        styles: StyleChain,
+    ) -> SourceResult<()> {
+        if let Some(lang) = self.lang(styles) {
+            if !self.knows_lang(lang, styles) {
+                engine.sink.warn(warning!(
+                    self.span(), "unknown language `{lang}`";
+                    hint: "the text will not be highlighted"
+                ));
+            }
+        }
+
         let seq = self.highlight(styles);
         self.push_lines(seq);
         Ok(())
@@ -302,6 +315,16 @@ impl Synthesize for Packed<RawElem> {
 }
 
 impl Packed<RawElem> {
+    /// Whether the language can be highlighted, either as Typst, with one of
+    /// the built-in syntaxes, or with one of the additional `syntaxes`.
+    fn knows_lang(&self, lang: &str, styles: StyleChain) -> bool {
+        let token = lang.to_lowercase();
+        matches!(token.as_str(), "typ" | "typst" | "typc")
+            || RAW_SYNTAXES.find_syntax_by_token(&token).is_some()
+            || load_syntaxes(&self.syntaxes(styles), &self.syntaxes_data(styles))
+                .is_ok_and(|set| set.find_syntax_by_token(&token).is_some())
+    }
+
     #[comemo::memoize]
     fn highlight(&self, styles: StyleChain) -> Vec<Packed<RawLine>> {
         let elem = self.as_ref();
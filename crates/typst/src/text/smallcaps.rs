@@ -31,8 +31,9 @@ use crate::text::TextElem;
 /// #show smallcaps: set text(font: "Latin Modern Roman Caps")
 /// ```
 ///
-/// In the future, this function will support synthesizing smallcaps from normal
-/// letters, but this is not yet implemented.
+/// If the font lacks the feature, Typst warns about it and shows the text in
+/// regular letters. In the future, this function will support synthesizing
+/// smallcaps from normal letters, but this is not yet implemented.
 #[elem(title = "Small Capitals", Show)]
 pub struct SmallcapsElem {
     /// The content to display in small capitals.
@@ -50,13 +50,18 @@ pub struct SmartQuoteElem {
     /// Whether to use alternative quotes.
     ///
     /// Does nothing for languages that don't have alternative quotes, or if
-    /// explicit quotes were set.
+    /// explicit quotes were set. For English, the alternative quotes follow
+    /// the British convention of single quotes on the outside and double
+    /// quotes inside.
     ///
     /// ```example
     /// #set text(lang: "de")
     /// #set smartquote(alternative: true)
     ///
     /// "Das ist in anderen Anführungszeichen."
+    ///
+    /// #set text(lang: "en")
+    /// "A 'British' quote."
     /// ```
     #[default(false)]
     pub alternative: bool,
@@ -219,6 +224,7 @@ impl<'s> SmartQuotes<'s> {
                 true => low_high,
             },
             "cs" | "da" | "de" | "sk" | "sl" if alternative => ("›", "‹", "»", "«"),
+            "en" if alternative => ("“", "”", "‘", "’"),
             "cs" | "de" | "et" | "is" | "lt" | "lv" | "sk" | "sl" => low_high,
             "da" => ("‘", "’", "“", "”"),
             "fr" | "ru" if alternative => default,
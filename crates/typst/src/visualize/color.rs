@@ -2011,4 +2011,54 @@ mod tests {
         test("hmmm", "color string contains non-hexadecimal letters");
         test("14B2AH", "color string contains non-hexadecimal letters");
     }
+
+    #[track_caller]
+    fn assert_close(a: Color, b: Color) {
+        let (a, b) = (a.to_rgb().to_vec4(), b.to_rgb().to_vec4());
+        assert!(a.iter().zip(b).all(|(x, y)| (x - y).abs() < 1e-4), "{a:?} != {b:?}");
+    }
+
+    #[test]
+    fn test_color_space_round_trips() {
+        let colors = ["000", "fff", "ff4136", "2ecc40", "0074d9", "b10dc9", "80808080"]
+            .map(|hex| Color::from_str(hex).unwrap());
+
+        // CMYK is converted to RGB through an ICC profile and thus doesn't
+        // round-trip. Luma only does for grays.
+        let spaces = [
+            ColorSpace::Oklab,
+            ColorSpace::Oklch,
+            ColorSpace::Srgb,
+            ColorSpace::LinearRgb,
+            ColorSpace::Hsl,
+            ColorSpace::Hsv,
+        ];
+
+        for color in colors {
+            for from in spaces {
+                let start = color.to_space(from);
+                for to in spaces {
+                    let converted = start.to_space(to);
+                    assert_eq!(converted.space(), to);
+                    assert_close(converted.to_space(from), start);
+                }
+            }
+        }
+
+        let gray = Color::from_str("808080").unwrap();
+        assert_close(gray.to_space(ColorSpace::D65Gray), gray);
+    }
+
+    #[test]
+    fn test_color_out_of_gamut() {
+        // A very saturated green that sRGB cannot represent clamps instead of
+        // panicking or wrapping around.
+        let color = Color::Oklch(Oklch::new(0.7, 0.4, 150.0, 1.0));
+        assert_eq!(color.to_hex(), "#00d600");
+        for space in
+            [ColorSpace::Srgb, ColorSpace::Hsl, ColorSpace::Hsv, ColorSpace::Cmyk]
+        {
+            color.to_space(space).to_vec4_u8();
+        }
+    }
 }
@@ -1249,6 +1249,13 @@ fn sample_stops(stops: &[(Color, Ratio)], mixing_space: ColorSpace, t: f64) -> C
 
     let (col_0, pos_0) = stops[low - 1];
     let (col_1, pos_1) = stops[low];
+
+    // The first stops may share an offset, in which case there is nothing to
+    // interpolate between and the later stop wins.
+    if pos_0 == pos_1 {
+        return col_1;
+    }
+
     let t = (t - pos_0.get()) / (pos_1.get() - pos_0.get());
 
     Color::mix_iter(
@@ -34,7 +34,8 @@ use crate::World;
 
 /// A raster or vector graphic.
 ///
-/// Supported formats are PNG, JPEG, GIF and SVG.
+/// Supported formats are PNG, JPEG, GIF, WebP and SVG. Of animated GIF and
+/// WebP images, only the first frame is shown.
 ///
 /// _Note:_ Work on SVG export is ongoing and there might be visual inaccuracies
 /// in the resulting PDF. Make sure to double-check embedded SVG images. If you
@@ -272,6 +273,7 @@ fn determine_format(path: &str, data: &Readable) -> StrResult<ImageFormat> {
         "png" => ImageFormat::Raster(RasterFormat::Png),
         "jpg" | "jpeg" => ImageFormat::Raster(RasterFormat::Jpg),
         "gif" => ImageFormat::Raster(RasterFormat::Gif),
+        "webp" => ImageFormat::Raster(RasterFormat::Webp),
         "svg" | "svgz" => ImageFormat::Vector(VectorFormat::Svg),
         _ => match &data {
             Readable::Str(_) => ImageFormat::Vector(VectorFormat::Svg),
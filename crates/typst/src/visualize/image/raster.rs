@@ -7,6 +7,7 @@ use ecow::{eco_format, EcoString};
 use image::codecs::gif::GifDecoder;
 use image::codecs::jpeg::JpegDecoder;
 use image::codecs::png::PngDecoder;
+use image::codecs::webp::WebPDecoder;
 use image::io::Limits;
 use image::{guess_format, DynamicImage, ImageDecoder, ImageResult};
 
@@ -45,6 +46,7 @@ impl RasterImage {
             RasterFormat::Jpg => decode_with(JpegDecoder::new(cursor)),
             RasterFormat::Png => decode_with(PngDecoder::new(cursor)),
             RasterFormat::Gif => decode_with(GifDecoder::new(cursor)),
+            RasterFormat::Webp => decode_with(WebPDecoder::new(cursor)),
         }
         .map_err(format_image_error)?;
 
@@ -116,6 +118,8 @@ pub enum RasterFormat {
     Jpg,
     /// Raster format that is typically used for short animated clips.
     Gif,
+    /// Raster format with lossy and lossless compression for the web.
+    Webp,
 }
 
 impl RasterFormat {
@@ -131,6 +135,7 @@ impl From<RasterFormat> for image::ImageFormat {
             RasterFormat::Png => image::ImageFormat::Png,
             RasterFormat::Jpg => image::ImageFormat::Jpeg,
             RasterFormat::Gif => image::ImageFormat::Gif,
+            RasterFormat::Webp => image::ImageFormat::WebP,
         }
     }
 }
@@ -143,6 +148,7 @@ impl TryFrom<image::ImageFormat> for RasterFormat {
             image::ImageFormat::Png => RasterFormat::Png,
             image::ImageFormat::Jpeg => RasterFormat::Jpg,
             image::ImageFormat::Gif => RasterFormat::Gif,
+            image::ImageFormat::WebP => RasterFormat::Webp,
             _ => bail!("Format not yet supported."),
         })
     }
@@ -284,4 +290,19 @@ mod tests {
         test("images/tiger.jpg", RasterFormat::Jpg, 72.0);
         test("images/graph.png", RasterFormat::Png, 144.0);
     }
+
+    #[test]
+    fn test_image_webp() {
+        #[track_caller]
+        fn test(data: &'static [u8]) {
+            assert_eq!(RasterFormat::detect(data), Some(RasterFormat::Webp));
+            let image =
+                RasterImage::new(Bytes::from_static(data), RasterFormat::Webp).unwrap();
+            assert_eq!((image.width(), image.height()), (1, 1));
+        }
+
+        // A lossless and a lossy image, each consisting of a single pixel.
+        test(b"RIFF\x1a\0\0\0WEBPVP8L\r\0\0\0/\0\0\0\x10\x07\x10\x11\x11\x88\x88\xfe\x07\0");
+        test(b"RIFF\"\0\0\0WEBPVP8 \x16\0\0\x000\x01\0\x9d\x01*\x01\0\x01\0\x0e\xc0\xfe%\xa4\0\x03p\0\0\0\0");
+    }
 }
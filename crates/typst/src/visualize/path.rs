@@ -8,9 +8,9 @@ use crate::foundations::{
 };
 use crate::introspection::Locator;
 use crate::layout::{
-    Abs, Axes, BlockElem, Frame, FrameItem, Length, Point, Region, Rel, Size,
+    Abs, Axes, BlockElem, Frame, FrameItem, Length, Point, Rect, Region, Rel, Size,
 };
-use crate::visualize::{FixedStroke, Geometry, Paint, Shape, Stroke};
+use crate::visualize::{FillRule, FixedStroke, Geometry, Paint, Shape, Stroke};
 
 use PathVertex::{AllControlPoints, MirroredControlPoint, Vertex};
 
@@ -33,11 +33,30 @@ pub struct PathElem {
     ///
     /// When setting a fill, the default stroke disappears. To create a
     /// rectangle with both fill and stroke, you have to configure both.
-    ///
-    /// Currently all paths are filled according to the [non-zero winding
-    /// rule](https://en.wikipedia.org/wiki/Nonzero-rule).
     pub fill: Option<Paint>,
 
+    /// The drawing rule used to fill the path.
+    ///
+    /// ```example
+    /// // We use `.with` to get a new
+    /// // function that has the common
+    /// // arguments pre-applied.
+    /// #let star = path.with(
+    ///   fill: red,
+    ///   closed: true,
+    ///   (25pt, 0pt),
+    ///   (10pt, 50pt),
+    ///   (50pt, 20pt),
+    ///   (0pt, 20pt),
+    ///   (40pt, 50pt),
+    /// )
+    ///
+    /// #star(fill-rule: "non-zero")
+    /// #star(fill-rule: "even-odd")
+    /// ```
+    #[default]
+    pub fill_rule: FillRule,
+
     /// How to [stroke] the path. This can be:
     ///
     /// Can be set to  `{none}` to disable the stroke or to `{auto}` for a
@@ -147,6 +166,7 @@ fn layout_path(
 
     // Prepare fill and stroke.
     let fill = elem.fill(styles);
+    let fill_rule = elem.fill_rule(styles);
     let stroke = match elem.stroke(styles) {
         Smart::Auto if fill.is_none() => Some(FixedStroke::default()),
         Smart::Auto => None,
@@ -154,7 +174,12 @@ fn layout_path(
     };
 
     let mut frame = Frame::soft(size);
-    let shape = Shape { geometry: Geometry::Path(path), stroke, fill };
+    let shape = Shape {
+        geometry: Geometry::Path(path),
+        stroke,
+        fill,
+        fill_rule,
+    };
     frame.push(Point::zero(), FrameItem::Shape(shape, elem.span()));
     Ok(frame)
 }
@@ -318,4 +343,44 @@ impl Path {
 
         Size::new(max_x - min_x, max_y - min_y)
     }
+
+    /// Computes the bounding box of the segments of this path relative to its
+    /// origin.
+    ///
+    /// Unlike [`bbox_size`](Self::bbox_size), this also yields where the box
+    /// is, which matters for paths that don't start at their origin.
+    pub fn bbox(&self) -> Rect {
+        let mut bbox: Option<Rect> = None;
+        let mut cursor = Point::zero();
+        for item in self.0.iter() {
+            let rect = match *item {
+                PathItem::MoveTo(to) => {
+                    cursor = to;
+                    continue;
+                }
+                PathItem::LineTo(to) => {
+                    let rect = Rect::new(cursor.min(to), cursor.max(to));
+                    cursor = to;
+                    rect
+                }
+                PathItem::CubicTo(c0, c1, end) => {
+                    let extrema = CubicBez::new(
+                        kurbo::Point::new(cursor.x.to_pt(), cursor.y.to_pt()),
+                        kurbo::Point::new(c0.x.to_pt(), c0.y.to_pt()),
+                        kurbo::Point::new(c1.x.to_pt(), c1.y.to_pt()),
+                        kurbo::Point::new(end.x.to_pt(), end.y.to_pt()),
+                    )
+                    .bounding_box();
+                    cursor = end;
+                    Rect::new(
+                        Point::new(Abs::pt(extrema.x0), Abs::pt(extrema.y0)),
+                        Point::new(Abs::pt(extrema.x1), Abs::pt(extrema.y1)),
+                    )
+                }
+                PathItem::ClosePath => continue,
+            };
+            bbox = Some(bbox.map_or(rect, |bbox| bbox.union(rect)));
+        }
+        bbox.unwrap_or_default()
+    }
 }
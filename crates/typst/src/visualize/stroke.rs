@@ -1,6 +1,6 @@
 use ecow::EcoString;
 
-use crate::diag::{HintedStrResult, SourceResult};
+use crate::diag::{bail, HintedStrResult, SourceResult};
 use crate::foundations::{
     cast, dict, func, scope, ty, Args, Cast, Dict, Fold, FromValue, NoneValue, Repr,
     Resolve, Smart, StyleChain, Value,
@@ -512,12 +512,17 @@ cast! {
 
     array: Vec<DashLength> => Self { array, phase: Length::zero() },
     mut dict: Dict => {
-        let array: Vec<DashLength> = dict.take("array")?.cast()?;
+        // Check for unexpected keys first, so that a misspelled `array` is
+        // reported as such instead of as a missing array.
+        let array = dict.take("array").ok();
         let phase = dict.take("phase").ok().map(Value::cast)
             .transpose()?.unwrap_or(Length::zero());
         dict.finish(&["array", "phase"])?;
+        let Some(array) = array else {
+            bail!("dash pattern must contain an array");
+        };
         Self {
-            array,
+            array: array.cast()?,
             phase,
         }
     },
@@ -61,7 +61,7 @@ impl Display for FileSize {
     }
 }
 
-/// An annotation like `// Error: 2-6 message` in a test.
+/// An annotation like `// Error: 2-6 message` or `// Text: Hello` in a test.
 pub struct Note {
     pub pos: FilePos,
     pub kind: NoteKind,
@@ -75,6 +75,7 @@ pub enum NoteKind {
     Error,
     Warning,
     Hint,
+    Text,
 }
 
 impl FromStr for NoteKind {
@@ -85,6 +86,7 @@ impl FromStr for NoteKind {
             "Error" => Self::Error,
             "Warning" => Self::Warning,
             "Hint" => Self::Hint,
+            "Text" => Self::Text,
             _ => return Err(()),
         })
     }
@@ -96,6 +98,7 @@ impl Display for NoteKind {
             Self::Error => "Error",
             Self::Warning => "Warning",
             Self::Hint => "Hint",
+            Self::Text => "Text",
         })
     }
 }
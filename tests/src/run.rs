@@ -6,10 +6,11 @@ use ecow::eco_vec;
 use tiny_skia as sk;
 use typst::diag::{SourceDiagnostic, Warned};
 use typst::foundations::Smart;
-use typst::layout::{Abs, Frame, FrameItem, Page, Transform};
+use typst::layout::{Abs, Em, Frame, FrameItem, Page, Transform};
 use typst::model::Document;
 use typst::visualize::Color;
 use typst::WorldExt;
+use typst_pdf::PdfStandard;
 
 use crate::collect::{FileSize, NoteKind, Test};
 use crate::world::TestWorld;
@@ -90,6 +91,7 @@ impl<'a> Runner<'a> {
         }
 
         self.check_document(doc.as_ref());
+        self.check_text(doc.as_ref());
 
         for error in &errors {
             self.check_diagnostic(NoteKind::Error, error);
@@ -149,8 +151,9 @@ impl<'a> Runner<'a> {
         };
 
         // Tests without visible output and no reference image don't need to be
-        // compared.
-        if skippable && !has_ref {
+        // compared. Neither do tests that check their text instead.
+        let checks_text = self.test.notes.iter().any(|note| note.kind == NoteKind::Text);
+        if (skippable || checks_text) && !has_ref {
             std::fs::remove_file(&live_path).ok();
             return;
         }
@@ -173,7 +176,9 @@ impl<'a> Runner<'a> {
         // Write PDF if requested.
         if crate::ARGS.pdf() {
             let pdf_path = format!("{}/pdf/{}.pdf", crate::STORE_PATH, self.test.name);
-            let pdf = typst_pdf::pdf(document, Smart::Auto, None, None);
+            let pdf =
+                typst_pdf::pdf(document, Smart::Auto, None, None, PdfStandard::V_1_7)
+                    .unwrap();
             std::fs::write(pdf_path, pdf).unwrap();
         }
 
@@ -235,6 +240,26 @@ impl<'a> Runner<'a> {
         }
     }
 
+    /// Compare the document's plain text against the `// Text:` annotations.
+    ///
+    /// Newlines in the text are written as `\n` in the annotation.
+    fn check_text(&mut self, document: Option<&Document>) {
+        let Some(document) = document else { return };
+        let text = document.text(Em::new(0.6)).replace('\n', "\\n");
+        for (i, note) in self.test.notes.iter().enumerate() {
+            if note.kind != NoteKind::Text {
+                continue;
+            }
+
+            self.seen[i] = true;
+            if note.message != text {
+                log!(self, "mismatched text ({})", note.pos);
+                log!(self, "  expected  | {}", note.message);
+                log!(self, "  extracted | {text}");
+            }
+        }
+    }
+
     /// Compare a subset of notes with a given kind against diagnostics of
     /// that same kind.
     fn check_diagnostic(&mut self, kind: NoteKind, diag: &SourceDiagnostic) {
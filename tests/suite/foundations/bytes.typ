@@ -29,3 +29,28 @@
 --- bytes-bad-conversion-from-dict ---
 // Error: 8-14 expected string, array, or bytes, found dictionary
 #bytes((a: 1))
+
+--- bytes-at ---
+#let data = bytes((10, 20, 30))
+#test(data.at(0), 10)
+#test(data.at(-1), 30)
+#test(data.at(3, default: none), none)
+#test(data.slice(1), bytes((20, 30)))
+#test(data.slice(-2, -1), bytes((20,)))
+
+--- bytes-at-out-of-bounds ---
+// Error: 2-24 byte index out of bounds (index: 3, len: 3) and no default value was specified
+#bytes((1, 2, 3)).at(3)
+
+--- bytes-equality ---
+#test(bytes("ABC"), bytes((65, 66, 67)))
+#test(bytes("ABC") == bytes("ABD"), false)
+#test(bytes(()) == bytes(""), true)
+
+--- bytes-bad-conversion-from-array ---
+// Error: 8-16 number must be between 0 and 255
+#bytes((1, 256))
+
+--- bytes-invalid-utf-8 ---
+// Error: 6-19 bytes are not valid utf-8
+#str(bytes((255,)))
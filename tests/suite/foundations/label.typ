@@ -39,9 +39,11 @@ This is a thing #[that <last>] happened.
 // Test that label ignores parbreak.
 #show <hide>: none
 
+// Warning: 1-9 label `<hide>` is attached to multiple elements
 _Hidden_
 <hide>
 
+// Warning: 1-9 label `<hide>` is attached to multiple elements
 _Hidden_
 
 <hide>
@@ -74,3 +76,17 @@ _Visible_
 // Hint: 7-7 labels can only be applied in markup mode
 // Hint: 7-7 try wrapping your code in a markup block (`[ ]`)
 #{ [A] <a> }
+
+--- label-duplicate ---
+// Each element that shares its label with another one is pointed out.
+// Warning: 2-13 label `<note>` is attached to multiple elements
+#metadata(1) <note>
+// Warning: 2-13 label `<note>` is attached to multiple elements
+#metadata(2) <note>
+#metadata(3) <other>
+
+--- label-duplicate-same-source ---
+// Elements from the same source location share a warning.
+// Warning: 22-33 label `<item>` is attached to multiple elements
+#for i in range(3) [#metadata(i) <item>]
+#context test(query(<item>).len(), 3)
@@ -28,6 +28,7 @@ Second: #context mine.display("I")
 // Count labels.
 #let label = <heya>
 #let count = context counter(label).display()
+// Warning: 19-26 label `<heya>` is attached to multiple elements
 #let elem(it) = [#box(it) #label]
 
 #elem[hey, there!] #count \
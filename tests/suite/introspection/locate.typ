@@ -16,10 +16,14 @@
 #context locate(<intro>)
 
 --- locate-duplicate-label ---
+// Warning: 1-15 label `<intro>` is attached to multiple elements
 = Introduction <intro>
+// Warning: 1-15 label `<intro>` is attached to multiple elements
 = Introduction <intro>
 
 // Error: 10-25 label `<intro>` occurs multiple times in the document
+// Hint: 10-25 heading on page 1 has this label
+// Hint: 10-25 heading on page 1 has this label
 #context locate(<intro>)
 
 --- locate-element-selector ---
@@ -32,7 +36,9 @@
 #context locate(heading)
 
 --- locate-element-selector-multiple-matches ---
+// Warning: 1-15 label `<intro>` is attached to multiple elements
 = Introduction <intro>
+// Warning: 1-15 label `<intro>` is attached to multiple elements
 = Introduction <intro>
 
 // Error: 10-25 selector matches multiple elements
@@ -148,7 +148,9 @@
 === D
 = E <first>
 #figure([Frog], kind: "frog", supplement: none)
+// Warning: 2-54 label `<second>` is attached to multiple elements
 #figure([Giraffe], kind: "giraffe", supplement: none) <second>
+// Warning: 2-56 label `<second>` is attached to multiple elements
 #figure([GiraffeCat], kind: "cat", supplement: [Other]) <second>
 = H
 #figure([Iguana], kind: "iguana", supplement: none)
@@ -203,6 +205,17 @@
   (),
 )
 
+// Test `where` with multiple fields, which needs no `and`.
+#test-selector(
+  figure.where(kind: "cat", supplement: [Other]),
+  ([Cat], [GiraffeCat]),
+)
+
+#test-selector(
+  figure.where(kind: "cat", supplement: none),
+  (),
+)
+
 // Test `or` with `before`/`after`
 #test-selector(
   selector(heading)
@@ -281,3 +294,86 @@
 // New show rules apply to this, but its location and the materialized fields
 // from the original are retained.
 #context query(heading).join()
+
+--- query-invisible-content ---
+// Content that is clipped away or placed outside of the page is still laid
+// out and thus found by queries.
+#place(hide[
+  // Warning: 46-57 label `<invisible>` is attached to multiple elements
+  #box(width: 0pt, height: 0pt, clip: true)[#metadata(1) <invisible>]
+  // Warning: 35-46 label `<invisible>` is attached to multiple elements
+  #place(dx: -500pt, dy: -500pt)[#metadata(2) <invisible>]
+  // Warning: 44-55 label `<invisible>` is attached to multiple elements
+  #box(height: 10pt, clip: true)[#v(20pt) #metadata(3) <invisible>]
+])
+
+#context {
+  let found = query(<invisible>)
+  test(found.map(it => it.value), (1, 2, 3))
+  test(found.at(1).location().position().y < 0pt, true)
+}
+
+--- query-descendant-of ---
+// Elements are descendants of an ancestor if they lie inside its block.
+#place(hide[
+  #block(width: 50pt)[
+    $a$ and $b$
+    #block[$c$] <inner>
+  ] <thm>
+  $d$
+  #block(width: 50pt)[$e$] <other>
+])
+
+#context {
+  let bodies(sel) = query(sel).map(it => it.body.text)
+  test(bodies(math.equation.descendant-of(<thm>)), ("a", "b", "c"))
+  test(bodies(math.equation.descendant-of(<inner>)), ("c",))
+  test(
+    bodies(math.equation.descendant-of(selector(<thm>).or(<other>))),
+    ("a", "b", "c", "e"),
+  )
+  test(query(selector(<thm>).descendant-of(<thm>)).len(), 0)
+  test(query(selector(<inner>).descendant-of(<thm>)).len(), 1)
+}
+
+--- query-descendant-of-repr ---
+#test(
+  repr(math.equation.descendant-of(<thm>)),
+  "equation.descendant-of(<thm>)",
+)
+
+--- query-descendant-of-show ---
+// Error: 7-41 this selector cannot be used with show
+#show math.equation.descendant-of(<thm>): none
+
+--- query-too-many-distinct ---
+// Error: layout performed too many distinct queries
+// Hint: at most 100000 distinct queries are allowed per layout pass
+// Hint: check if a selector is created anew in a loop
+#context for i in range(100001) {
+  query(label("l" + str(i)))
+}
+
+--- query-transformed ---
+#place(hide[
+  #rotate(45deg)[#metadata(1)]
+  #scale(50%)[#metadata(2)]
+  #metadata(3)
+])
+
+#context {
+  let rotated = metadata.transformed(rotated: true)
+  let scaled = metadata.transformed(scaled-below: 100%)
+  test(query(rotated).map(it => it.value), (1,))
+  test(query(scaled).map(it => it.value), (2,))
+  test(repr(rotated), "metadata.transformed(rotated: true)")
+  test(repr(scaled), "metadata.transformed(scaled-below: 100%)")
+}
+
+--- query-transformed-no-condition ---
+// Error: 2-24 expected `rotated: true` or `scaled-below`
+#metadata.transformed()
+
+--- query-transformed-both-conditions ---
+// Error: 2-56 cannot combine `rotated` and `scaled-below`
+#metadata.transformed(rotated: true, scaled-below: 50%)
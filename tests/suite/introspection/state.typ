@@ -51,10 +51,25 @@ Was: #context {
 // Make sure that a warning is produced if the layout fails to converge.
 // Warning: layout did not converge within 5 attempts
 // Hint: check if any states or queries are updating themselves
+// Hint: the results of the query for `state-update.where(key: "s")` kept changing
 #let s = state("s", 1)
 #context s.update(s.final() + 1)
 #context s.get()
 
+--- state-feedback-converges ---
+// A state that feeds its final value back into itself converges as soon as
+// the value stops changing.
+#let s = state("feedback", 0)
+#context s.update(calc.min(s.final() + 1, 3))
+#context test(s.final(), 3)
+
+--- state-feedback-converges-counter ---
+// The same for a counter whose final value determines how often it is
+// stepped.
+#let c = counter("feedback")
+#context for _ in range(calc.min(c.final().first() + 1, 3)) { c.step() }
+#context test(c.final(), (3,))
+
 --- state-at-no-context ---
 // Test `state.at` outside of context.
 // Error: 2-26 can only be used when context is known
@@ -16,7 +16,7 @@
 // Test the `columns` function.
 #set page(width: auto)
 
-#rect(width: 180pt, height: 100pt, inset: 8pt, columns(2, [
+#rect(width: 180pt, height: 100pt, inset: 8pt, columns(2, balance: false, [
     A special plight has befallen our document.
     Columns in text boxes reigned down unto the soil
     to waste a year's crop of rich layouts.
@@ -40,7 +40,7 @@ a page for a test but it does get the job done.
 // Test the expansion behaviour.
 #set page(height: 2.5cm, width: 7.05cm)
 
-#rect(inset: 6pt, columns(2, [
+#rect(inset: 6pt, columns(2, balance: false, [
     ABC \
     BCD
     #colbreak()
@@ -116,9 +116,92 @@ In flow.
 #set page(height: 70pt)
 
 Hallo
-#columns(2)[
+#columns(2, balance: false)[
   = A
   Text
   = B
   Text
 ]
+
+--- columns-balance ---
+// Columns are balanced by default, so that the content is split evenly instead
+// of filling the first column.
+#let items = range(4).map(i => block(height: 30pt, breakable: false, metadata(i)))
+
+#set block(spacing: 0pt)
+#place(hide(box(height: 100pt, width: 100pt, columns(2, balance: false, items.join()))))
+#place(hide(box(height: 100pt, width: 100pt, columns(2, items.join()))))
+
+#context {
+  let xs = query(metadata).map(it => it.location().position().x)
+  // Unbalanced: three items in the first column.
+  test(xs.slice(0, 3).dedup().len(), 1)
+  test(xs.at(2) < xs.at(3), true)
+  // Balanced: two items in each column.
+  test(xs.at(4), xs.at(5))
+  test(xs.at(6), xs.at(7))
+  test(xs.at(5) < xs.at(6), true)
+}
+
+--- columns-balance-page ---
+// The columns of a page are not balanced.
+#set page(height: 20pt, margin: 0pt, columns: 2)
+#set block(spacing: 0pt)
+#block(height: 5pt, metadata(1))
+#block(height: 5pt, metadata(2))
+#context test(query(metadata).map(it => it.location().position().x), (0pt, 0pt))
+
+--- columns-balance-float ---
+// A bottom float goes to the bottom of its column, which is where the balanced
+// columns end.
+#set page(height: 20pt, margin: 0pt)
+#set block(spacing: 0pt)
+#columns(2)[
+  #place(bottom, float: true, clearance: 0pt)[#metadata(none) <float>]
+  #block(height: 8pt, breakable: false)
+  #block(height: 8pt, breakable: false)
+]
+
+#context {
+  let pos = locate(<float>).position()
+  test(pos.x, 0pt)
+  test(pos.y > 0pt and pos.y < 12pt, true)
+}
+
+--- columns-balance-footnote ---
+// A footnote entry goes to the bottom of the column that references it, which
+// is where the balanced columns end.
+#set page(height: 20pt, margin: 0pt)
+#set block(spacing: 0pt)
+#set footnote.entry(separator: none, clearance: 0pt, gap: 0pt)
+#show footnote.entry: it => [#metadata(none) <entry>]
+#columns(2)[
+  #block(height: 8pt, breakable: false)
+  #block(height: 8pt, breakable: false, hide(footnote[Note]))
+]
+
+#context {
+  let pos = locate(<entry>).position()
+  test(pos.x > 50pt, true)
+  test(pos.y > 0pt and pos.y < 12pt, true)
+}
+
+--- columns-balance-heading ---
+// A heading that doesn't fit into the first column together with its body
+// moves to the second one, also in balanced columns.
+#set page(height: 20pt, margin: 0pt)
+#set block(spacing: 0pt)
+#set text(size: 4pt)
+#hide(columns(2)[
+  #block(height: 12pt, breakable: false)
+  = Heading <heading>
+  #block(height: 8pt, breakable: false) <body>
+])
+
+#context {
+  let heading = locate(<heading>).position()
+  let body = locate(<body>).position()
+  test(heading.x > 50pt, true)
+  test(heading.x, body.x)
+  test(heading.y < body.y, true)
+}
@@ -201,3 +201,12 @@ First!
 // Test box in 100% width block.
 #block(width: 100%, fill: red, box("a box"))
 #block(width: 100%, fill: red, [#box("a box") #box()])
+
+--- block-unbreakable-moves-to-next-region ---
+// An unbreakable block that does not fit moves to the next region as a whole.
+#place(hide(box(width: 100pt, height: 50pt, columns(2)[
+  #block(height: 30pt)
+  #block(height: 30pt, breakable: false)[#metadata(none) <moved>]
+])))
+
+#context test(locate(<moved>).position().x > 50pt, true)
@@ -283,3 +283,34 @@ The following:
   // Error: 3-50 cannot create grid with infinite width
   grid(gutter: infinite-length, columns: 2)[A][B]
 })
+
+--- grid-auto-and-fr-columns ---
+// Fractional columns get the space that is left after sizing auto columns.
+#place(hide(block(width: 100pt, grid(
+  columns: (auto, 1fr, auto),
+  box(width: 20pt),
+  layout(size => test(size.width, 70pt)),
+  box(width: 10pt),
+))))
+
+--- grid-auto-columns-overflowing ---
+// If auto columns claim all available space, fractional columns collapse to
+// zero width instead of becoming negative.
+#place(hide(block(width: 100pt, grid(
+  columns: (auto, 1fr),
+  box(width: 150pt),
+  layout(size => test(size.width, 0pt)),
+))))
+
+--- grid-auto-and-fr-columns-nested ---
+// Nested grids size their columns relative to the enclosing cell, also when
+// an auto column contains an image with a relative width.
+#place(hide(block(width: 100pt, grid(
+  columns: (40pt, 1fr),
+  [],
+  grid(
+    columns: (auto, 1fr),
+    image("/assets/images/cylinder.svg", width: 50%),
+    layout(size => test(size.width, 30pt)),
+  ),
+))))
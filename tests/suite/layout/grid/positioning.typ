@@ -201,3 +201,18 @@
   // Robert got grade B on other exams.
   ..(table.cell(y: 4, fill: aqua)[B],) * 2,
 )
+
+--- table-cell-position-after-spans ---
+// Cells following a spanning cell are placed into the next free slot.
+#show table.cell: it => {
+  let expected = ("A": (0, 0), "B": (2, 0), "C": (2, 1), "D": (0, 2))
+  test((it.x, it.y), expected.at(it.body.text))
+  it
+}
+
+#place(hide(table(
+  columns: 3,
+  table.cell(colspan: 2, rowspan: 2)[A], [B],
+  [C],
+  [D],
+)))
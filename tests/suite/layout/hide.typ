@@ -4,6 +4,16 @@
 AB #h(1fr) CD \
 #hide[A]B #h(1fr) C#hide[D]
 
+--- hide-same-layout ---
+// Hidden content takes exactly the space of the visible content.
+#context {
+  let blank = box(width: 2cm, stroke: (bottom: 0.5pt))[answer]
+  let body = [Fill in the #blank here.]
+  test(measure(hide(body)), measure(body))
+  test(measure([A #hide[hidden] B]), measure([A hidden B]))
+  test(measure(hide(link("https://typst.app")[Link])), measure([Link]))
+}
+
 --- hide-line ---
 Hidden:
 #hide[#line(length: 100%)]
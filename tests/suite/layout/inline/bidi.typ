@@ -72,6 +72,8 @@ Lריווח #h(1cm) R
 --- issue-1373-bidi-tofus ---
 // Test that shaping missing characters in both left-to-right and
 // right-to-left directions does not cause a crash.
+// Warning: 2-32 missing glyphs for "/u{590}/u{591}/u{592}/u{593}"
 #"\u{590}\u{591}\u{592}\u{593}"
 
+// Warning: 2-40 missing glyphs for "𰀀𰀁𰀂𰀃"
 #"\u{30000}\u{30001}\u{30002}\u{30003}"
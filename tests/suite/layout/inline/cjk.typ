@@ -24,6 +24,25 @@
 
 中文，中ab文a中，文ab中文
 
+--- cjk-linebreak-between-characters ---
+// CJK runs can be broken between any two characters.
+#set text(lang: "ja", font: "Noto Serif CJK JP")
+#context test(
+  measure(block(width: 2em)[日本日本]).height,
+  measure[日本 \ 日本].height,
+)
+#context test(
+  measure(block(width: 3em)[日本語text]).height,
+  measure[日本語 \ text].height,
+)
+
+--- cjk-linebreak-kinsoku ---
+// Closing punctuation never starts a line and opening punctuation never ends
+// one.
+#set text(lang: "ja", font: "Noto Serif CJK JP")
+#context test(measure(block(width: 1em)[日。]).height, measure[日].height)
+#context test(measure(block(width: 1em)[「日]).height, measure[日].height)
+
 --- cjk-punctuation-adjustment-1 ---
 #set page(width: 15em)
 
@@ -22,3 +22,26 @@
 #set align(end)
 #set text(dir: rtl)
 :
+
+--- overhang-narrow-column ---
+// In a narrow justified column, an opening quote at the start of a line and
+// punctuation at its end hang into the margins.
+#let column(overhang) = place(hide(block(width: 45mm)[
+  #set par(justify: true)
+  #set text(overhang: overhang)
+  #metadata("open")"Quoted words,#metadata("end")#linebreak(justify: true)
+  #metadata("start")then plain words follow.
+]))
+
+#column(true)
+#column(false)
+
+#context {
+  let xs = query(metadata).map(it => it.location().position().x)
+  let (open, end, start) = xs.slice(0, 3)
+  test(open < start, true)
+  test(end - start > 45mm, true)
+  let (open, end, start) = xs.slice(3)
+  test(open, start)
+  test(calc.round((end - start - 45mm).pt(), digits: 6), 0.0)
+}
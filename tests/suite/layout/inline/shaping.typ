@@ -45,8 +45,17 @@ Aب😀🏞سمB
 01️⃣2
 
 // Tofus are rendered with the first font.
+// Warning: 1-6 missing glyphs for "ዲሞ"
 A🐈ዲሞB
 
+--- shaping-missing-glyphs-warning ---
+// Characters without a glyph in any font are listed once per piece of text.
+#set text(fallback: false)
+// Warning: 7-10 missing glyphs for "هذا"
+#hide[هذا]
+// Warning: 7-10 missing glyphs for "ዲሞ"
+#hide[ዲሞዲ]
+
 --- shaping-emoji-basic ---
 // This should form a three-member family.
 👩‍👩‍👦
@@ -38,6 +38,20 @@ fi vs. #text(ligatures: false)[No fi]
 #text(features: ("smcp",))[Smcp] \
 fi vs. #text(features: (liga: 0))[No fi]
 
+--- text-features-nested ---
+// Features enabled by nested set rules combine.
+#set text(font: "IBM Plex Serif")
+#context {
+  let nested = {
+    set text(slashed-zero: true)
+    set text(fractions: true)
+    [1/2 0]
+  }
+  let combined = text(slashed-zero: true, fractions: true)[1/2 0]
+  test(measure(nested), measure(combined))
+  test(measure(nested) == measure[1/2 0], false)
+}
+
 --- text-stylistic-set-bad-type ---
 // Error: 26-31 expected integer or none, found boolean
 #set text(stylistic-set: false)
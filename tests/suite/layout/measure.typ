@@ -8,6 +8,21 @@
 #text(10pt, f(6pt, 8pt))
 #text(20pt, f(13pt, 14pt))
 
+--- measure-under-set-text ---
+// Test that `measure` picks up text sizes from set rules in scope.
+#set text(size: 5pt)
+#context assert(measure[Hello].height < 5pt)
+#set text(size: 20pt)
+#context assert(measure[Hello].height > 10pt)
+
+--- measure-with-style ---
+// Test the deprecated `style` function handing the active styles to `measure`.
+#set text(size: 20pt)
+#style(styles => {
+  let size = measure([Hello], styles)
+  assert(size.height > 10pt)
+})
+
 --- measure-given-area ---
 // Test `measure` given an area.
 #let text = lorem(100)
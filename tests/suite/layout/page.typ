@@ -266,3 +266,10 @@ Hi
 #set page(fill: gray)
 text
 #pagebreak()
+
+--- page-set-on-empty-page ---
+// A page set rule on a page that is still empty does not create a new page.
+#set page(width: 80pt, height: 80pt)
+#set page(width: 120pt, height: 20pt)
+#context test(here().page(), 1)
+#context test(counter(page).final(), (1,))
@@ -84,7 +84,7 @@ Second
 --- place-float-columns ---
 // LARGE
 #set page(height: 200pt, width: 300pt)
-#show: columns.with(2)
+#show: columns.with(2, balance: false)
 
 = Introduction
 #figure(
@@ -251,3 +251,18 @@ Start.
 )
 
 #lorem(20)
+
+--- place-float-final-position ---
+// The introspector reports where floats end up, not where they are defined.
+#place(hide(box(width: 100pt, height: 100pt)[
+  #metadata(none) <before>
+  #block(height: 40pt)
+  #place(top, float: true)[#metadata(none) <top>]
+  #place(bottom, float: true)[#metadata(none) <bottom>]
+]))
+
+#context {
+  let y(label) = locate(label).position().y
+  test(y(<top>) <= y(<before>), true)
+  test(y(<bottom>) > y(<before>) + 40pt, true)
+}
@@ -104,3 +104,19 @@ Hello #scaled[World]!\
 
 #set scale(reflow: true)
 Hello #scaled[World]!
+
+--- transform-rotate-reflow-grid ---
+// A rotated cell only makes its row taller if the rotation reflows, no matter
+// which origin it rotates around.
+#let head(..args) = rotate(90deg, ..args, box(width: 30pt, height: 10pt))
+#let row(cell) = measure(grid(columns: 2, cell, box(width: 5pt, height: 5pt)))
+#let approx(a, b) = test(calc.abs((a - b).pt()) < 1e-6, true)
+#context for origin in (center + horizon, top + left, bottom + right) {
+  let off = row(head(origin: origin))
+  approx(off.width, 35pt)
+  approx(off.height, 10pt)
+
+  let on = row(head(origin: origin, reflow: true))
+  approx(on.width, 15pt)
+  approx(on.height, 30pt)
+}
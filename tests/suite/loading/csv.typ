@@ -25,3 +25,14 @@
 // Test error numbering with dictionary rows.
 // Error: 6-28 failed to parse CSV (found 3 instead of 2 fields in line 3)
 #csv("/assets/data/bad.csv", row-type: dictionary)
+
+--- csv-delimiter ---
+// Test a custom delimiter.
+#let data = csv.decode(bytes("Name;Age\nDebby;4\n"), delimiter: ";")
+#test(data, (("Name", "Age"), ("Debby", "4")))
+#let data = csv.decode(
+  bytes("Name;Age\nDebby;4\n"),
+  delimiter: ";",
+  row-type: dictionary,
+)
+#test(data, ((Name: "Debby", Age: "4"),))
@@ -10,3 +10,10 @@
 --- read-invalid-utf-8 ---
 // Error: 18-40 file is not valid utf-8
 #let data = read("/assets/text/bad.txt")
+
+--- read-bytes ---
+// Test reading files as raw bytes.
+#let data = read("/assets/text/hello.txt", encoding: none)
+#test(type(data), bytes)
+#test(data.len(), 14)
+#test(str(data), "Hello, world!\n")
@@ -128,3 +128,23 @@ $integral.sect_a^b quad \u{2a1b}_a^b quad limits(\u{2a1b})_a^b$
 // Test default of limit attachments on large operators at display size only
 $ tack.t.big_0^1 quad \u{02A0A}_0^1 quad join_0^1 $
 $tack.t.big_0^1 quad \u{02A0A}_0^1 quad join_0^1$
+
+--- math-attach-sum-display-vs-inline ---
+// Big operators take limits in display style and scripts inline, which makes
+// the display version taller and the inline version wider.
+#context {
+  let display = measure($ sum_(i=0)^n $)
+  let inline = measure($sum_(i=0)^n$)
+  test(display.height > inline.height, true)
+  test(measure($ scripts(sum)_(i=0)^n $).width > display.width, true)
+}
+
+--- math-attach-all-positions ---
+// Each attachment position adds to the size of the base.
+#context {
+  let base = measure($ attach(A) $)
+  for pos in ("t", "b", "tl", "tr", "bl", "br") {
+    let attached = measure(math.equation(block: true, math.attach([A], ..((pos): [x]))))
+    test(attached.width >= base.width and attached.height > base.height, true)
+  }
+}
@@ -213,6 +213,25 @@ $ mat(delim: angle.r, 1, 2; 3, 4) $
 $ mat(delim: #(none, "["), 1, 2; 3, 4) $
 $ mat(delim: #(sym.angle.r, sym.bracket.double.r), 1, 2; 3, 4) $
 
+--- math-mat-nested-scales ---
+// Delimiters of nested matrices scale to the height of their content.
+#context {
+  let inner = measure($ mat(1, 2; 3, 4) $).height
+  let outer = measure($ mat(mat(1, 2; 3, 4), 0; 0, mat(1, 2; 3, 4)) $).height
+  test(outer > 2 * inner, true)
+}
+
+--- math-mat-tall-and-wide ---
+// Tall matrices grow vertically and wide ones horizontally.
+#context {
+  let small = measure($ mat(1, 2; 3, 4) $)
+  let tall = measure($ mat(1; 2; 3; 4; 5; 6; 7; 8) $)
+  let wide = measure($ mat(1, 2, 3, 4, 5, 6, 7, 8) $)
+  test(tall.height > 2 * small.height, true)
+  test(wide.width > 2 * small.width, true)
+  test(wide.height < small.height, true)
+}
+
 --- issue-1617-mat-align ---
 #set page(width: auto)
 $ mat(a, b; c, d) mat(x; y) $
@@ -130,6 +130,16 @@ $ a &+ b + & c \
     &      & c && + d \
     &= 0 $
 
+--- math-multiline-par-leading ---
+// The spacing between the lines of an equation follows the leading.
+#context {
+  let eq = $ x &= a + b \ &= b + a \ &= c $
+  let tight = measure({ set par(leading: 0.2em); eq }).height
+  let loose = measure({ set par(leading: 1em); eq }).height
+  let diff = loose - tight - 2 * 0.8em.to-absolute()
+  test(calc.abs(diff.pt()) < 0.01, true)
+}
+
 --- issue-1948-math-text-break ---
 // Test text with linebreaks in math.
 $ x := "a\nb\nc\nd\ne" $
@@ -43,3 +43,13 @@ $ root(2, x) quad
 $ √2^3 = sqrt(2^3) $
 $ √(x+y) quad ∛x quad ∜x $
 $ (√2+3) = (sqrt(2)+3) $
+
+--- math-root-index-width ---
+// The index of a root is placed before the radical sign and widens the root.
+#context {
+  let plain = measure($ sqrt(x) $)
+  let cubic = measure($ root(3, x) $)
+  let high = measure($ root(123, x) $)
+  test(cubic.width >= plain.width, true)
+  test(high.width > cubic.width, true)
+}
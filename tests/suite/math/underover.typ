@@ -19,3 +19,15 @@ $ x = overbracket(
 $ underbracket([1, 2/3], "relevant stuff")
           arrow.l.r.double.long
   overbracket([4/5,6], "irrelevant stuff") $
+
+--- math-underover-brace-wide-base ---
+// The brace stretches over a wide base and the annotation doesn't widen it.
+#context {
+  let base = $ 1 + 2 + 3 + 4 + 5 + 6 + 7 + 8 + 9 + 10 $
+  let plain = measure(base)
+  let over = measure($ overbrace(#base.body, "ten") $)
+  let under = measure($ underbrace(#base.body, "ten") $)
+  test(calc.abs((over.width - plain.width).pt()) < 0.01, true)
+  test(over.height > plain.height, true)
+  test(over.height, under.height)
+}
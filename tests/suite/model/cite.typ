@@ -126,3 +126,14 @@ B #cite(<netwok>) #cite(<arrgh>).
 // Error: 7-17 expected label, found string
 // Hint: 7-17 use `label("%@&#*!\\")` to create a label
 #cite("%@&#*!\\")
+
+--- cite-unknown-key ---
+// Error: 2-21 key `nonexistent` does not exist in the bibliography
+#cite(<nonexistent>)
+#bibliography("/assets/bib/works.bib")
+
+--- cite-unknown-key-ref ---
+// A reference to an unknown key is not turned into a citation.
+// Error: 1-13 label `<nonexistent>` does not exist in the document
+@nonexistent
+#bibliography("/assets/bib/works.bib")
@@ -34,3 +34,14 @@ Hello
   // Error: 4-32 document set rules are not allowed inside of containers
   #set document(title: [Hello])
 ]
+
+--- document-text ---
+// Text: Hello world\n\nSecond paragraph
+Hello world
+
+Second paragraph
+
+--- document-text-placed ---
+// Placed content interrupts the paragraph, but is read after the flow.
+// Text: Hello\n\nworld\n\nNote
+Hello #place(top)[Note] world
@@ -47,6 +47,17 @@ a + 0.
    enum.item(5)[Fifth]
 )
 
+--- enum-number-override-continues ---
+// Automatic numbering continues after an explicit number.
+#context test(
+  measure[
+    7. a
+    + b
+    + c
+  ],
+  measure(enum(start: 7)[a][b][c]),
+)
+
 --- enum-numbering-pattern ---
 // Test numbering pattern.
 #set enum(numbering: "(1.a.*)")
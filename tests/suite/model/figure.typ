@@ -75,6 +75,7 @@ We can clearly see that @fig-cylinder and
 }
 
 #set page(width: 150pt)
+// Warning: 2-7:2 label `<fig-formula>` is attached to multiple elements
 #figure(
   $a^2 + b^2 = c^2$,
   supplement: "Theorem",
@@ -83,6 +84,7 @@ We can clearly see that @fig-cylinder and
   numbering: "1",
 ) <fig-formula>
 
+// Warning: 2-7:2 label `<fig-formula>` is attached to multiple elements
 #figure(
   $a^2 + b^2 = c^2$,
   supplement: "Theorem",
@@ -226,3 +228,31 @@ HI#footnote.entry(clearance: 2.5em)[There]
 // Test that figure caption separator is synthesized correctly.
 #show figure.caption: c => test(c.separator, [#": "])
 #figure(table[], caption: [This is a test caption])
+
+--- figure-kind-counters ---
+// Each kind of figure is numbered with its own counter.
+#place(hide[
+  #figure(table[A])
+  #figure(image("/assets/images/cylinder.svg", height: 1pt))
+  #figure(table[B]) <second-table>
+  #figure(raw("x"))
+])
+
+#context {
+  test(query(figure).map(it => it.kind), (table, image, table, raw))
+  test(counter(figure.where(kind: table)).at(<second-table>), (2,))
+  test(counter(figure.where(kind: image)).final(), (1,))
+  test(counter(figure.where(kind: raw)).final(), (1,))
+}
+
+--- figure-where-kind-show ---
+// A show rule for table figures leaves other figures alone.
+#let hits = counter("hits")
+#show figure.where(kind: table): it => hits.step() + it
+#place(hide[
+  #figure(table[A])
+  #figure(rect())
+  #figure(table[B])
+])
+
+#context test(hits.final(), (2,))
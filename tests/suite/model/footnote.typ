@@ -53,7 +53,7 @@ Beautiful footnotes. #footnote[Wonderful, aren't they?]
 // Test footnotes in columns, even those that are not enabled via `set page`.
 #set page(height: 120pt)
 #align(center, strong[Title])
-#show: columns.with(2)
+#show: columns.with(2, balance: false)
 #lorem(3) #footnote(lorem(6))
 Hello there #footnote(lorem(2))
 
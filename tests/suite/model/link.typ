@@ -66,12 +66,30 @@ My cool #box(move(dx: 0.7cm, dy: 0.7cm, rotate(10deg, scale(200%, mylink))))
 Text <hey>
 #link(<hey>)[Go to text.]
 
+--- link-query ---
+// Links are locatable, so internal and external links can be queried.
+#place(hide[
+  = Intro <intro>
+  See #link("https://typst.app")[Typst] and go #link(<intro>)[back].
+])
+
+#context {
+  let links = query(link)
+  test(links.map(it => it.dest), ("https://typst.app", <intro>))
+  test(query(link.where(dest: <intro>)).len(), 1)
+  test(links.first().location().page(), 1)
+}
+
 --- link-to-label-missing ---
 // Error: 2-20 label `<hey>` does not exist in the document
 #link(<hey>)[Nope.]
 
 --- link-to-label-duplicate ---
+// Warning: 1-5 label `<hey>` is attached to multiple elements
 Text <hey>
+// Warning: 1-5 label `<hey>` is attached to multiple elements
 Text <hey>
 // Error: 2-20 label `<hey>` occurs multiple times in the document
+// Hint: 2-20 text on page 1 has this label
+// Hint: 2-20 text on page 1 has this label
 #link(<hey>)[Nope.]
@@ -69,6 +69,28 @@ Not in list
   - B
     - C
 
+--- list-marker-cycle-three-levels ---
+// The third level starts the cycle over.
+#let nested = list(list(list[A]))
+#context test(
+  measure({ set list(marker: ([--], [•])); nested }),
+  measure({ set list(marker: ([--], [•], [--])); nested }),
+)
+
+--- list-tight-vs-wide ---
+// Wide lists are separated by paragraph spacing instead of leading.
+#context assert(
+  measure[
+    - A
+    - B
+  ].height
+    < measure[
+    - A
+
+    - B
+  ].height
+)
+
 --- list-marker-closure ---
 // Test function.
 #set list(marker: n => if n == 1 [--] else [•])
@@ -57,6 +57,30 @@ starts a paragraph, also with indent.
 
 ثم يصبح النص رطبًا وقابل للطرق ويبدو المستند رائعًا.
 
+--- par-first-line-indent-all ---
+// With `all`, the first paragraph in a container is indented, too.
+#let indented(body) = {
+  set par(first-line-indent: (amount: 10pt, all: true))
+  body
+}
+
+#context {
+  test(measure(indented[abc]).width, measure[abc].width + 10pt)
+  test(measure(indented(list[abc])).width, measure(list[abc]).width + 10pt)
+  test(
+    measure(indented(quote(block: true)[abc])).width,
+    measure(quote(block: true)[abc]).width + 10pt,
+  )
+}
+
+--- par-first-line-indent-dict ---
+#set par(first-line-indent: 1em)
+#context test(par.first-line-indent, (amount: 1em, all: false))
+
+--- par-first-line-indent-bad-key ---
+// Error: 29-55 unexpected key "every", valid keys are "amount" and "all"
+#set par(first-line-indent: (amount: 1em, every: true))
+
 --- par-spacing-and-first-line-indent ---
 // This is madness.
 #set par(first-line-indent: 12pt)
@@ -64,6 +88,28 @@ Why would anybody ever ...
 
 ... want spacing and indent?
 
+--- par-tracking-limit ---
+// Tracking only fills justified lines, it doesn't change the line breaks.
+#set par(justify: true)
+#let body = [Extraordinarily long words in narrow columns are hard.]
+#context test(
+  measure(block(width: 60pt, { set par(tracking-limit: 1pt); body })).height,
+  measure(block(width: 60pt, body)).height,
+)
+
+--- par-tracking-limit-bounded ---
+// Without spaces to stretch, a justified line only gets as much tracking as
+// the limit allows. The last letter gets none.
+#set par(justify: true, tracking-limit: 1pt)
+#place(hide[
+  #metadata(none)<start>ABCD#metadata(none)<end>#linebreak(justify: true)
+])
+
+#context {
+  let width = locate(<end>).position().x - locate(<start>).position().x
+  test(calc.round((width - measure[ABCD].width).pt(), digits: 6), 3.0)
+}
+
 --- par-hanging-indent ---
 // Test hanging indent.
 #set par(hanging-indent: 15pt, justify: true)
@@ -14,10 +14,14 @@ As seen in @intro, we proceed.
 @foo
 
 --- ref-label-duplicate ---
+// Warning: 1-8 label `<foo>` is attached to multiple elements
 = First <foo>
+// Warning: 1-9 label `<foo>` is attached to multiple elements
 = Second <foo>
 
 // Error: 1-5 label `<foo>` occurs multiple times in the document
+// Hint: 1-5 heading on page 1 has this label
+// Hint: 1-5 heading on page 1 has this label
 @foo
 
 --- ref-supplements ---
@@ -7,6 +7,40 @@
   ([Two], [Second]),
 )
 
+--- terms-markup-grouped ---
+// Adjacent items are laid out as one list, even if show rules restyle them.
+#let markup = [
+  / A: B
+  / C: D
+]
+#let constructed = terms(([A], [B]), ([C], [D]))
+#context {
+  test(measure(markup), measure(constructed))
+  test(measure({ show terms.item: it => text(red, it); markup }), measure(constructed))
+}
+
+--- terms-item-show ---
+// Show rules can replace individual items.
+#context test(
+  measure({
+    show terms.item: box(width: 5pt, height: 10pt)
+    [/ A: B]
+  }).height,
+  10pt,
+)
+
+--- terms-item-show-fields ---
+// The term can be arbitrary content.
+#context {
+  let _ = measure({
+    show terms.item: it => {
+      test(it.term, strong[Bold])
+      test(it.description, [A description.])
+    }
+    [/ *Bold*: A description.]
+  })
+}
+
 --- terms-built-in-loop ---
 // Test joining.
 #for word in lorem(4).split().map(s => s.trim(".")) [
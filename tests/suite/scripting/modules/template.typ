@@ -0,0 +1,6 @@
+// SKIP
+// A minimal document template that records its arguments instead of
+// rendering them.
+#let template(title: "Untitled", body) = {
+  metadata((title: title, body: body))
+}
@@ -373,8 +373,13 @@
 
 --- ops-divide-em-by-abs ---
 // Error: 3-12 cannot divide these two lengths
+// Hint: 3-12 use `.to-absolute()` within a context to resolve their em units first
 #(1em / 5pt)
 
+--- ops-divide-em-by-abs-in-context ---
+#set text(size: 10pt)
+#context test((1em).to-absolute() / 5pt, 2.0)
+
 --- ops-divide-relative-length-by-ratio ---
 // Error: 3-19 cannot divide relative length by ratio
 #((10% + 1pt) / 5%)
@@ -36,6 +36,52 @@ Hello *#x*
   [Not blue]
 }
 
+--- set-scoped-in-content-block ---
+// Test that set rules don't leak out of content blocks.
+#[#set text(red); #context test(text.fill, red)]
+#context test(text.fill, black)
+
+--- set-default-without-set ---
+#set default text(size: 20pt)
+#context test(text.size, 20pt)
+
+--- set-default-loses-against-inner-set ---
+#set default text(size: 20pt)
+#set text(size: 5pt)
+#context test(text.size, 5pt)
+
+--- set-default-loses-against-outer-set ---
+// A template's defaults yield to the user's styles even when the template is
+// applied after them.
+#let template(body) = {
+  set default text(size: 20pt)
+  body
+}
+#set text(size: 5pt)
+#show: template
+#context test(text.size, 5pt)
+
+--- set-default-inner-wins-among-defaults ---
+#set default text(size: 20pt)
+#[
+  #set default text(size: 15pt)
+  #context test(text.size, 15pt)
+]
+#context test(text.size, 20pt)
+
+--- set-default-in-show-set ---
+#show strong: it => context test(text.size, 20pt)
+#show strong: set default text(size: 20pt)
+*Strong*
+
+--- set-default-identifier ---
+// Outside of the modifier position, `default` is a regular identifier.
+#let default = text
+#set default(size: 20pt)
+#context test(text.size, 20pt)
+#let default = 1
+#test(default + 1, 2)
+
 --- closure-path-resolve-in-layout-phase ---
 // Test relative path resolving in layout phase.
 #let choice = ("monkey.svg", "rhino.png", "tiger.jpg")
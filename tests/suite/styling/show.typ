@@ -135,6 +135,23 @@ Ignored
 // Error: 4-19 show is only allowed directly in code and content blocks
 #((show: body => 2) * body)
 
+--- show-bare-in-argument ---
+// Error: 8-22 show is only allowed directly in code and content blocks
+#text((show: it => it))
+
+--- show-bare-template-import ---
+// Test an imported template applied via `show` with `.with`. Consecutive bare
+// show rules are applied innermost-first.
+#import "../scripting/modules/template.typ": template
+#context {
+  let meta = query(metadata).first().value
+  test(meta.title, "Report")
+  test(meta.body.func(), strong)
+}
+#show: template.with(title: "Report")
+#show: strong
+Body
+
 --- show-bare-missing-colon-closure ---
 // Error: 6 expected colon
 #show it => {}
@@ -260,3 +277,28 @@ I am *strong*, I am _emphasized_, and I am #[special<special>].
 
 = Hello
 *strong*
+
+--- show-selector-where-reemit ---
+// A transform that re-emits the matched element must not be applied to its
+// own output again.
+#let hits = counter("hits")
+#show heading.where(level: 1): it => { hits.step(); it }
+#place(hide[= One])
+#place(hide[== Two])
+#context test(hits.final().at(0), 1)
+
+--- show-selector-label-reemit ---
+// Same for label selectors, even when the output is wrapped.
+#let hits = counter("hits")
+#show <special>: it => { hits.step(); box(it) }
+#place(hide[#strong[A] <special>])
+#context test(hits.final().at(0), 1)
+
+--- show-selector-reemit-with-set ---
+// Set rules inside the transform apply to the re-emitted element.
+#show heading: it => context test(text.size, 5pt)
+#show heading: it => {
+  set text(size: 5pt)
+  it
+}
+#place(hide[= Heading])
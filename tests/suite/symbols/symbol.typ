@@ -36,3 +36,28 @@
 --- symbol-unknown-modifier ---
 // Error: 13-20 unknown symbol modifier
 #emoji.face.garbage
+
+--- symbol-unknown-modifier-hint ---
+#let envelope = symbol(
+  "🖂",
+  ("stamped", "🖃"),
+  ("stamped.pen", "🖆"),
+  ("lightning", "🖄"),
+  ("fly", "🖅"),
+)
+
+// Error: 11-18 unknown symbol modifier
+// Hint: 11-18 valid modifiers are `fly`, `lightning`, `pen`, and `stamped`
+#envelope.garbage
+
+--- symbol-unknown-modifier-hint-nested ---
+#let envelope = symbol(
+  "🖂",
+  ("stamped", "🖃"),
+  ("stamped.pen", "🖆"),
+  ("lightning", "🖄"),
+)
+
+// Error: 19-23 unknown symbol modifier
+// Hint: 19-23 valid modifiers are `pen`
+#envelope.stamped.pend
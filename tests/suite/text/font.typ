@@ -39,6 +39,8 @@ Emoji: 🐪, 🌋, 🏞
 // Disable font fallback beyond the user-specified list.
 // Without disabling, New Computer Modern Math would come to the rescue.
 #set text(font: ("PT Sans", "Twitter Color Emoji"), fallback: false)
+// Warning: 6-7 missing glyphs for "𝛼"
+// Warning: 10-12 missing glyphs for "𝛽"
 2π = 𝛼 + 𝛽. ✅
 
 --- text-call-body ---
@@ -25,6 +25,15 @@
   text(lang: "de")["Eingabeaufforderung"],
 )
 
+--- text-lang-hyphenate-soft-hyphen ---
+// Words with soft hyphens are only hyphenated at those.
+#set text(hyphenate: true)
+#set par(linebreaks: "simple")
+#context test(
+  measure(block(width: 1pt)[Eingabe#sym.hyph.soft;aufforderung]).height,
+  measure(block(width: 1pt)[Eingabe \ aufforderung]).height,
+)
+
 --- text-lang-shaping ---
 // Test that the language passed to the shaper has an effect.
 #set text(font: "Ubuntu")
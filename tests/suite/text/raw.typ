@@ -330,6 +330,47 @@ test
   block: true,
 )
 
+// A tag directly followed by the closing backticks is still a tag
+#let lang-only = (
+  name: "lang-only",
+  input: ```js```,
+  lang: "js",
+  text: "",
+  block: false,
+)
+
+// A tag must be followed by whitespace
+#let lang-punct = (
+  name: "lang-punct",
+  input: ```js;x```,
+  text: "js;x",
+  block: false,
+)
+
+// More backticks can enclose three backticks
+#let nested-backticks = (
+  name: "nested-backticks",
+  input: ````
+```js
+x
+```
+````,
+  text: "```js\nx\n```",
+  block: true,
+)
+
+// Exactly one leading and trailing newline is stripped
+#let blocky-blank-lines = (
+  name: "blocky-blank-lines",
+  input: ```
+
+x
+
+```,
+  text: "\nx\n",
+  block: true,
+)
+
 // The first line and the last line are ignored
 #let blocky = (
   name: "blocky",
@@ -445,12 +486,19 @@ test
   blocky-dedent-lastline2,
   blocky-tab,
   blocky-tab-dedent,
+  lang-only,
+  lang-punct,
+  nested-backticks,
+  blocky-blank-lines,
 )
 
 #for c in cases {
   assert.eq(c.text, c.input.text, message: "in point " + c.name + ", expect " + repr(c.text) + ", got " + repr(c.input.text) + "")
   let block = c.at("block", default: false)
   assert.eq(block, c.input.block, message: "in point " + c.name + ", expect " + repr(block) + ", got " + repr(c.input.block) + "")
+  let lang = c.at("lang", default: none)
+  let actual = c.input.at("lang", default: none)
+  assert.eq(lang, actual, message: "in point " + c.name + ", expect " + repr(lang) + ", got " + repr(actual) + "")
 }
 
 --- raw-line ---
@@ -654,6 +702,11 @@ fn main() {
 #let foo = "bar"
 ```
 
+--- raw-unknown-lang ---
+// Warning: 28-57 unknown language `nonexistent`
+// Hint: 28-57 the text will not be highlighted
+#context { let _ = measure(raw(lang: "nonexistent", "x")) }
+
 --- raw-unclosed ---
 // Test unterminated raw text.
 //
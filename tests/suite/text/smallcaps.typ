@@ -6,7 +6,27 @@
 // There is no dedicated smallcaps font in typst-dev-assets, so we just use some
 // other font to test this show rule.
 #show smallcaps: set text(font: "PT Sans")
+// Warning: 12-21 font family "PT Sans" does not support small capitals
+// Hint: 12-21 lowercase letters are shown in their regular form
 #smallcaps[Smallcaps]
 
 #show smallcaps: set text(fill: red)
+// Warning: 12-21 font family "PT Sans" does not support small capitals
+// Hint: 12-21 lowercase letters are shown in their regular form
 #smallcaps[Smallcaps]
+
+--- smallcaps-supported ---
+// Fonts with small capitals don't warn. This includes Roboto, which other
+// tests use for small capitals, too.
+#place(hide[
+  #smallcaps[Libertine]
+  #text(font: "Roboto", smallcaps[Roboto])
+])
+
+--- smallcaps-unsupported ---
+// Warns for lowercase letters in a font without small capitals, but not for
+// uppercase ones.
+#place(hide(text(font: "PT Sans", smallcaps[PT])))
+// Warning: 45-52 font family "PT Sans" does not support small capitals
+// Hint: 45-52 lowercase letters are shown in their regular form
+#place(hide(text(font: "PT Sans", smallcaps[Regular])))
@@ -69,6 +69,21 @@ Some people's thought on this would be #[#set smartquote(enabled: false); "stran
 "'test' statement" \
 "statement 'test'"
 
+--- smartquote-english-alternative ---
+// English alternative quotes put single quotes on the outside. The outer and
+// inner quotes are measured separately since swapping them keeps the total
+// width.
+#set smartquote(alternative: true)
+#context {
+  test(measure["a].width, measure[‘a].width)
+  test(measure["'b'].width, measure[‘“b”].width)
+  test(measure["a].width == measure[“a].width, false)
+}
+
+--- smartquote-across-strong ---
+// Quote state is tracked across inline elements.
+#context test(measure[*"a"* "*b*" it's].width, measure[*“a”* “*b*” it’s].width)
+
 --- smartquote-custom ---
 // Use language quotes for missing keys, allow partial reset
 #set smartquote(quotes: "«»")
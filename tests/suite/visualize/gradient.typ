@@ -565,6 +565,22 @@ $ A = mat(
 #test(gradient.linear(red, green, blue, space: rgb).sample(75%), rgb("#17a08c"))
 #test(gradient.linear(red, green, blue, space: rgb).sample(100%), blue)
 
+--- gradient-sample-coinciding-stops ---
+// Stops at the same offset yield the later stop instead of an invalid mix.
+#let g = gradient.linear((red, 0%), (blue, 0%), (green, 100%), space: rgb)
+#test(g.sample(0%), blue)
+#test(g.sample(50%), blue.mix(green, space: rgb))
+#test(g.sample(100%), green)
+
+--- gradient-radial-functions ---
+#let g = gradient.radial(red, green, blue, space: rgb)
+#test(g.kind(), gradient.radial)
+#test(g.stops(), ((red, 0%), (green, 50%), (blue, 100%)))
+#test(g.space(), rgb)
+#test(g.relative(), auto)
+#test(g.angle(), none)
+#test(g.samples(0%, 50%, 100%), (red, green, blue))
+
 --- gradient-space ---
 #test(gradient.linear(red, green, space: rgb).space(), rgb)
 #test(gradient.linear(red, green, space: oklab).space(), oklab)
@@ -88,6 +88,13 @@ A #box(image("/assets/images/tiger.jpg", height: 1cm, width: 80%)) B
 // Test parsing from svg data
 #image.decode(`<svg xmlns="http://www.w3.org/2000/svg" height="140" width="500"><ellipse cx="200" cy="80" rx="100" ry="50" style="fill:yellow;stroke:purple;stroke-width:2" /></svg>`.text, format: "svg")
 
+--- image-decode-svg-natural-size ---
+// An SVG's natural size follows from its width and height at 96 DPI.
+#context {
+  let svg = `<svg xmlns="http://www.w3.org/2000/svg" width="96" height="48"></svg>`.text
+  test(measure(image.decode(svg, format: "svg")), (width: 72pt, height: 36pt))
+}
+
 --- image-decode-bad-svg ---
 // Error: 2-168 failed to parse SVG (missing root node)
 #image.decode(`<svg height="140" width="500"><ellipse cx="200" cy="80" rx="100" ry="50" style="fill:yellow;stroke:purple;stroke-width:2" /></svg>`.text, format: "svg")
@@ -81,6 +81,14 @@
 // Error: 29-55 expected "solid", "dotted", "densely-dotted", "loosely-dotted", "dashed", "densely-dashed", "loosely-dashed", "dash-dotted", "densely-dash-dotted", "loosely-dash-dotted", array, dictionary, none, or auto
 #line(length: 60pt, stroke: (paint: red, dash: "dash"))
 
+--- line-stroke-dash-field-typo ---
+// Error: 29-68 unexpected key "arary", valid keys are "array" and "phase"
+#line(length: 60pt, stroke: (dash: (arary: (1pt, 2pt), phase: 1pt)))
+
+--- line-stroke-dash-missing-array ---
+// Error: 29-49 dash pattern must contain an array
+#line(length: 60pt, stroke: (dash: (phase: 1pt)))
+
 --- line-bad-point-array ---
 // Test errors.
 
@@ -51,6 +51,10 @@
 // Error: 7-31 point array must contain exactly two entries
 #path(((0%, 0%), (0%, 0%, 0%)))
 
+--- path-bad-fill-rule ---
+// Error: 18-23 expected "non-zero" or "even-odd"
+#path(fill-rule: "foo", (0pt, 0pt))
+
 --- issue-path-in-sized-container ---
 // Paths used to implement `LayoutMultiple` rather than `LayoutSingle` without
 // fulfilling the necessary contract of respecting region expansion.